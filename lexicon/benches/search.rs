@@ -0,0 +1,77 @@
+//! Benchmarks the searches of a [`ScoredWordTrie`] over the full word list.
+//!
+//! Run with `cargo bench -p lexicon`.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use lexicon::ScoredWordTrie;
+use std::hint::black_box;
+use std::path::Path;
+use std::sync::LazyLock;
+
+/// The word list shipped with lexiterm, loaded once for every benchmark.
+static WORD_TRIE: LazyLock<ScoredWordTrie> = LazyLock::new(|| {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    ScoredWordTrie::new_from_files(&root.join("words.txt"), &root.join("char_scores.txt"))
+        .expect("the word list should be readable")
+});
+
+/// Racks of 7, 10, and 15 tiles, with and without wildcards.
+const RACKS: [&str; 6] = [
+    "retains",
+    "retain*",
+    "painterly*",
+    "painterlys",
+    "unfathomablesty",
+    "unfathomables**",
+];
+
+fn bench_get_words(c: &mut Criterion) {
+    let trie = &*WORD_TRIE;
+    let mut group = c.benchmark_group("get_words");
+    group.sample_size(10);
+
+    for rack in RACKS {
+        group.bench_with_input(BenchmarkId::from_parameter(rack), rack, |b, rack| {
+            b.iter(|| trie.get_words(black_box(rack)))
+        });
+    }
+    group.finish();
+}
+
+fn bench_get_word_matches(c: &mut Criterion) {
+    let trie = &*WORD_TRIE;
+    let mut group = c.benchmark_group("get_word_matches");
+    group.sample_size(10);
+
+    for rack in RACKS {
+        group.bench_with_input(BenchmarkId::from_parameter(rack), rack, |b, rack| {
+            b.iter(|| trie.get_word_matches(black_box(rack), "^[^aeiou]+ing$"))
+        });
+    }
+    group.finish();
+}
+
+fn bench_parallel(c: &mut Criterion) {
+    let trie = &*WORD_TRIE;
+    let mut group = c.benchmark_group("parallel");
+    group.sample_size(10);
+
+    for rack in RACKS {
+        group.bench_with_input(BenchmarkId::new("sequential", rack), rack, |b, rack| {
+            b.iter(|| trie.word_trie.iter_words(black_box(rack)).count())
+        });
+        #[cfg(feature = "parallel")]
+        group.bench_with_input(BenchmarkId::new("parallel", rack), rack, |b, rack| {
+            b.iter(|| trie.word_trie.get_words_parallel(black_box(rack)).len())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_get_words,
+    bench_get_word_matches,
+    bench_parallel
+);
+criterion_main!(benches);
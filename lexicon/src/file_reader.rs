@@ -1,20 +1,31 @@
 use super::*;
+use flate2::read::MultiGzDecoder;
 use std::char;
 use std::collections::HashMap;
 use std::fs::File;
+use std::io;
 use std::io::prelude::*;
 use std::io::BufReader;
 use std::path::Path;
 
-pub struct FileReader {
-    reader: BufReader<File>,
+/// The byte order mark that can start a UTF-8 file.
+const BOM: char = '\u{feff}';
+
+/// The first bytes of every gzip file.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+pub struct FileReader<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    /// Where the lines are read from, for error messages.
+    source: String,
 }
 
 /// Reads words from a file.
 ///
 /// # Usage
 ///
-/// This reader the file line by line
+/// This reader the file line by line. Files ending with `.gz` or starting with the gzip
+/// magic bytes are decompressed while reading.
 ///
 /// ```ignore
 /// use std::path::Path;
@@ -26,14 +37,35 @@ pub struct FileReader {
 ///     println!("{line}");
 /// }
 /// ```
-impl FileReader {
+impl<'a> FileReader<'a> {
     fn new(path: &Path) -> Result<Self, OpenFileError> {
-        let file = File::open(path).map_err(|err| OpenFileError {
+        let open_err = |err| OpenFileError {
             path: path.to_string_lossy().to_string(),
             err,
-        })?;
-        let reader = BufReader::new(file);
-        Ok(Self { reader })
+        };
+        let mut file = BufReader::new(File::open(path).map_err(open_err)?);
+
+        let is_gzip = path.extension().is_some_and(|ext| ext == "gz")
+            || file.fill_buf().map_err(open_err)?.starts_with(&GZIP_MAGIC);
+        let reader: Box<dyn BufRead> = if is_gzip {
+            Box::new(BufReader::new(MultiGzDecoder::new(file)))
+        } else {
+            Box::new(file)
+        };
+
+        Ok(Self {
+            reader,
+            source: path.to_string_lossy().to_string(),
+        })
+    }
+
+    /// Reads the lines from any reader instead of a file, such as stdin or an in-memory
+    /// buffer.
+    pub fn from_reader(reader: impl BufRead + 'a) -> Self {
+        Self {
+            reader: Box::new(reader),
+            source: "<reader>".to_string(),
+        }
     }
 
     /// Returns an iterator of strings over a words file.
@@ -48,13 +80,25 @@ impl FileReader {
     /// aardwolf
     /// aardwolves
     /// ```
-    pub fn parse_word_file(path: &Path) -> Result<Vec<String>, ParseWordFileError> {
-        let reader = FileReader::new(path)?;
+    pub fn parse_word_file(
+        path: &Path,
+        is_valid_char: impl Fn(char) -> bool,
+    ) -> Result<Vec<String>, ParseWordFileError> {
+        FileReader::new(path)?.parse_words(is_valid_char)
+    }
+
+    /// Same as [`FileReader::parse_word_file`] but reads the words from this reader.
+    pub fn parse_words(
+        self,
+        is_valid_char: impl Fn(char) -> bool,
+    ) -> Result<Vec<String>, ParseWordFileError> {
+        let source = self.source.clone();
 
         let mut words = Vec::new();
-        for word in reader.into_iter() {
-            if word.chars().any(|ch| !ch.is_ascii_alphabetic()) {
-                return Err(ParseWordFileError::InvalidWord(word));
+        for (i, word) in self.into_iter().enumerate() {
+            let word = word.map_err(|err| ReadFileError::new(&source, i + 1, err))?;
+            if word.chars().any(|ch| !is_valid_char(ch)) {
+                return Err(ParseWordFileError::InvalidWord(i + 1, word));
             }
             words.push(word);
         }
@@ -62,77 +106,118 @@ impl FileReader {
         Ok(words)
     }
 
-    /// Returns an iterator of strings over a words file.
+    /// Parses the scores of each letter from a scores file.
     ///
-    /// # Example Words File
+    /// Blank lines and lines starting with `#` are ignored. Scores can be zero or
+    /// negative for penalty tiles.
+    ///
+    /// # Example Scores File
     ///
     /// ```txt
+    /// # vowels
     /// a=1
+    /// e=1
+    ///
+    /// # consonants
     /// b=3
     /// c=3
     /// d=2
-    /// e=1
     /// f=4
     /// ```
-    pub fn parse_scores_file(path: &Path) -> Result<HashMap<char, u8>, ParseScoreFileError> {
+    pub fn parse_scores_file(
+        path: &Path,
+        alphabet: Alphabet,
+    ) -> Result<HashMap<char, i32>, ParseScoreFileError> {
         let mut scores = HashMap::new();
         let reader = FileReader::new(path)?;
+        let source = reader.source.clone();
 
         for (i, line_str) in reader.into_iter().enumerate() {
-            let (ch_str, score_str) = line_str
-                .split_once('=')
-                .ok_or(ParseScoreFileError::MissingEqualSign(i, line_str.clone()))?;
+            let line = i + 1;
+            let line_str = line_str.map_err(|err| ReadFileError::new(&source, line, err))?;
+            let trimmed = line_str.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let (ch_str, score_str) =
+                line_str
+                    .split_once('=')
+                    .ok_or(ParseScoreFileError::MissingEqualSign(
+                        line,
+                        line_str.clone(),
+                    ))?;
 
-            if ch_str.len() != 1 {
+            let mut chars = ch_str.chars();
+            let (Some(ch), None) = (chars.next(), chars.next()) else {
                 return Err(ParseScoreFileError::InvalidChar(ch_str.to_string()));
-            }
+            };
 
-            let ch = ch_str.chars().next().unwrap();
-            if !ch.is_ascii_alphabetic() {
+            if !alphabet.is_letter(ch) {
                 return Err(ParseScoreFileError::InvalidChar(ch_str.to_string()));
             }
 
             let score = score_str
-                .parse::<u8>()
+                .parse::<i32>()
                 .map_err(|err| ParseScoreFileError::InvalidScore(score_str.to_string(), err))?;
 
-            scores.insert(ch, score);
+            if scores.insert(ch, score).is_some() {
+                return Err(ParseScoreFileError::DuplicateChar(line, ch));
+            }
         }
 
         Ok(scores)
     }
 }
 
-impl IntoIterator for FileReader {
-    type Item = String;
-    type IntoIter = FileLineIterator;
+impl<'a> IntoIterator for FileReader<'a> {
+    type Item = io::Result<String>;
+    type IntoIter = FileLineIterator<'a>;
 
     fn into_iter(self) -> Self::IntoIter {
         FileLineIterator {
             reader: self.reader,
+            at_start: true,
+            failed: false,
         }
     }
 }
 
-pub struct FileLineIterator {
-    reader: BufReader<File>,
+pub struct FileLineIterator<'a> {
+    reader: Box<dyn BufRead + 'a>,
+    at_start: bool,
+    failed: bool,
 }
 
-impl Iterator for FileLineIterator {
-    type Item = String;
+impl Iterator for FileLineIterator<'_> {
+    type Item = io::Result<String>;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
         let mut word = String::new();
-        let len = self
-            .reader
-            .read_line(&mut word)
-            .expect("read line from file");
 
-        if len == 0 {
-            None
-        } else {
-            // Trim is done to remove the newline character at the end
-            Some(word.trim_end().to_string())
+        match self.reader.read_line(&mut word) {
+            Ok(0) => None,
+            Ok(_) => {
+                // Editors on Windows often save files with a byte order mark
+                let mut line = word.as_str();
+                if std::mem::take(&mut self.at_start) {
+                    line = line.strip_prefix(BOM).unwrap_or(line);
+                }
+
+                // Trim is done to remove the newline character at the end, including
+                // the `\r` of CRLF line endings
+                Some(Ok(line.trim_end().to_string()))
+            }
+            Err(err) => {
+                // A failed read may leave the reader at any point in the line, so
+                // nothing after it can be trusted
+                self.failed = true;
+                Some(Err(err))
+            }
         }
     }
 }
@@ -153,7 +238,7 @@ mod tests {
             FileReader::new(Path::new("../words.txt")).expect("should load words from file");
 
         for (n, word) in reader.into_iter().enumerate() {
-            words.push(word);
+            words.push(word.expect("should read the line"));
             if n + 1 == n_words {
                 break;
             }
@@ -161,4 +246,143 @@ mod tests {
 
         assert_eq!(words, expected_words)
     }
+
+    #[test]
+    fn can_parse_scores_file_with_comments() {
+        let scores = FileReader::parse_scores_file(
+            Path::new("./test_data/commented_scores.txt"),
+            Alphabet::Ascii,
+        )
+        .expect("should skip comments and blank lines");
+
+        assert_eq!(
+            scores,
+            HashMap::from([('a', 1), ('e', 1), ('b', 3), ('c', 3)])
+        );
+    }
+
+    #[test]
+    fn can_parse_scores_file() {
+        let scores =
+            FileReader::parse_scores_file(Path::new("../char_scores.txt"), Alphabet::Ascii)
+                .expect("should parse the scores file");
+
+        assert_eq!(scores.len(), 26);
+        assert_eq!(scores.get(&'a'), Some(&1));
+    }
+
+    #[test]
+    fn can_parse_zero_and_negative_scores() {
+        let scores = FileReader::parse_scores_file(
+            Path::new("./test_data/signed_scores.txt"),
+            Alphabet::Ascii,
+        )
+        .expect("should parse signed scores");
+
+        assert_eq!(scores, HashMap::from([('a', 1), ('b', 0), ('x', -3)]));
+    }
+
+    #[test]
+    fn rejects_duplicate_chars_in_scores_file() {
+        let err = FileReader::parse_scores_file(
+            Path::new("./test_data/duplicate_scores.txt"),
+            Alphabet::Ascii,
+        )
+        .expect_err("`a` is defined twice");
+
+        assert!(matches!(err, ParseScoreFileError::DuplicateChar(4, 'a')));
+    }
+
+    #[test]
+    fn can_parse_accented_words_file() {
+        let path = Path::new("./test_data/accented_words.txt");
+
+        let words = FileReader::parse_word_file(path, |ch| Alphabet::Unicode.is_letter(ch))
+            .expect("should parse the file");
+        assert_eq!(words, ["café", "niño", "Été", "nina", "thé"]);
+
+        let err = FileReader::parse_word_file(path, |ch| Alphabet::Ascii.is_letter(ch))
+            .expect_err("accented letters are not ASCII");
+        assert!(matches!(err, ParseWordFileError::InvalidWord(1, word) if word == "café"));
+    }
+
+    #[test]
+    fn can_parse_punctuated_words_file() {
+        let is_valid_char = |ch: char| ch.is_ascii_alphabetic() || ['\'', '-'].contains(&ch);
+
+        let words = FileReader::parse_word_file(
+            Path::new("./test_data/punctuated_words.txt"),
+            is_valid_char,
+        )
+        .expect("should parse the file");
+        assert_eq!(words, ["dont", "don't", "mother-in-law", "cat"]);
+
+        let err =
+            FileReader::parse_word_file(Path::new("./test_data/invalid_words.txt"), is_valid_char)
+                .expect_err("digits are not allowed");
+        assert!(matches!(&err, ParseWordFileError::InvalidWord(4, word) if word == "r2d2"));
+        assert_eq!(
+            err.to_string(),
+            "Invalid word on line 4: \"r2d2\". Words can only contain letters and the allowed punctuation."
+        );
+    }
+
+    #[test]
+    fn reports_read_errors() {
+        let path = Path::new("./test_data/invalid_utf8_words.txt");
+
+        let mut lines = FileReader::new(path)
+            .expect("should open the file")
+            .into_iter();
+        assert_eq!(lines.next().map(Result::ok), Some(Some("cat".to_string())));
+        assert!(matches!(lines.next(), Some(Err(err)) if err.kind() == io::ErrorKind::InvalidData));
+        assert!(lines.next().is_none());
+
+        let err = FileReader::parse_word_file(path, |ch| ch.is_ascii_alphabetic())
+            .expect_err("line 2 is not valid UTF-8");
+        assert!(matches!(
+            err,
+            ParseWordFileError::ReadFile(ReadFileError { line: 2, .. })
+        ));
+
+        let err = FileReader::parse_scores_file(path, Alphabet::Ascii)
+            .expect_err("line 1 has no equal sign");
+        assert!(matches!(err, ParseScoreFileError::MissingEqualSign(1, _)));
+    }
+
+    #[test]
+    fn can_parse_gzipped_words_file() {
+        let is_valid_char = |ch: char| ch.is_ascii_alphabetic();
+
+        let words =
+            FileReader::parse_word_file(Path::new("./test_data/words.txt.gz"), is_valid_char)
+                .expect("should decompress the file");
+        let expected =
+            FileReader::parse_word_file(Path::new("./test_data/words.txt"), is_valid_char)
+                .expect("should parse the file");
+
+        assert_eq!(words, expected);
+    }
+
+    #[test]
+    fn can_parse_words_from_reader() {
+        let input = "cat\r\nact\ntac\n".as_bytes();
+
+        let words = FileReader::from_reader(input)
+            .parse_words(|ch| ch.is_ascii_alphabetic())
+            .expect("should parse the words");
+
+        assert_eq!(words, ["cat", "act", "tac"]);
+    }
+
+    #[test]
+    fn strips_bom_and_crlf() {
+        let words =
+            FileReader::parse_word_file(Path::new("./test_data/bom_crlf_words.txt"), |ch| {
+                ch.is_ascii_alphabetic()
+            })
+            .expect("should parse the file");
+
+        assert_eq!(words, ["cat", "act", "dog"]);
+    }
 }
@@ -0,0 +1,121 @@
+use super::{ScoredWordTrie, WordTrie};
+
+/// A word found by a [`Lexicon`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WordResult {
+    pub word: String,
+    /// The score of the word or `None` if the [`Lexicon`] doesn't score words.
+    pub score: Option<u32>,
+}
+
+/// A dictionary that can be searched for the words buildable from a rack, whether it
+/// scores them or not.
+///
+/// This lets callers swap a [`WordTrie`] for a [`ScoredWordTrie`] without changing how
+/// they search.
+pub trait Lexicon {
+    /// Gets all the words that could be built using the given letters.
+    fn words(&self, letters: &str) -> Vec<WordResult>;
+}
+
+impl Lexicon for WordTrie {
+    fn words(&self, letters: &str) -> Vec<WordResult> {
+        self.get_words(letters)
+            .into_iter()
+            .map(|word| WordResult { word, score: None })
+            .collect()
+    }
+}
+
+impl Lexicon for ScoredWordTrie {
+    /// Gets all the words that could be built using the given letters sorted by score.
+    fn words(&self, letters: &str) -> Vec<WordResult> {
+        self.get_words(letters)
+            .into_iter()
+            .map(|(word, score)| WordResult {
+                word,
+                score: Some(score),
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::collections::HashMap;
+    use std::path::Path;
+
+    #[test]
+    pub fn test_lexicon_trait_objects() {
+        let new_word_trie = || {
+            let mut word_trie = WordTrie::default();
+            ["cat", "act"].iter().for_each(|w| word_trie.insert(w));
+            word_trie
+        };
+        let word_trie = new_word_trie();
+        let scored_word_trie = ScoredWordTrie {
+            word_trie: new_word_trie(),
+            score_map: HashMap::from([('c', 3), ('a', 1), ('t', 1)]),
+            ..Default::default()
+        };
+        let lexicons: [&dyn Lexicon; 2] = [&word_trie, &scored_word_trie];
+
+        let results = lexicons
+            .iter()
+            .map(|lexicon| {
+                let mut words = lexicon.words("tac");
+                words.sort_by(|a, b| a.word.cmp(&b.word));
+                words
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            results,
+            [
+                vec![
+                    WordResult {
+                        word: "act".to_string(),
+                        score: None
+                    },
+                    WordResult {
+                        word: "cat".to_string(),
+                        score: None
+                    },
+                ],
+                vec![
+                    WordResult {
+                        word: "act".to_string(),
+                        score: Some(5)
+                    },
+                    WordResult {
+                        word: "cat".to_string(),
+                        score: Some(5)
+                    },
+                ],
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_lexicons_agree_on_fixture() {
+        let path = Path::new("./test_data/words.txt");
+        let word_trie = WordTrie::new_from_file(path).expect("fixture loads");
+        let scored_word_trie = ScoredWordTrie::with_scrabble_scores(path).expect("fixture loads");
+
+        for letters in ["radar", "radical*", "cart", "jukebox", "**"] {
+            let words = |lexicon: &dyn Lexicon| {
+                let mut words = lexicon
+                    .words(letters)
+                    .into_iter()
+                    .map(|result| result.word)
+                    .collect::<Vec<_>>();
+                words.sort();
+                words
+            };
+
+            assert_eq!(words(&word_trie), words(&scored_word_trie), "{letters:?}");
+        }
+    }
+}
@@ -1,35 +1,180 @@
 mod file_reader;
+mod lexicon;
 mod scored_word_trie;
 mod word_trie;
 
 use file_reader::FileReader;
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
 use std::{num::ParseIntError, path::Path};
 use thiserror::Error;
 
-pub use scored_word_trie::ScoredWordTrie;
-pub use word_trie::WordTrie;
+pub use lexicon::{Lexicon, WordResult};
+pub use scored_word_trie::{
+    ResultOrdering, ScoredWordTrie, ScoredWordTrieBuilder, TileMultiplier, SCRABBLE_SCORES,
+    WORDS_WITH_FRIENDS_SCORES,
+};
+pub use word_trie::{
+    Alphabet, Dawg, FoundWord, SearchSession, Tile, WordTrie, DEFAULT_PUNCTUATION,
+};
 
 impl WordTrie {
     pub fn new_from_file(path: &Path) -> Result<Self, ParseFileError> {
+        Self::new_from_file_with_alphabet(path, Alphabet::Ascii)
+    }
+
+    /// Creates a Trie from the words of several words files, such as a base dictionary
+    /// and a custom word list. Words found in more than one file are only kept once.
+    pub fn new_from_files(paths: &[&Path]) -> Result<Self, ParseFileError> {
         let mut word_trie = WordTrie::default();
-        let words = FileReader::parse_word_file(path)?;
+        word_trie.insert_from_files(paths.iter().copied())?;
 
-        for word in words.into_iter() {
-            word_trie.insert(&word);
+        Ok(word_trie)
+    }
+
+    /// Inserts the words of each words file, naming the file in the error of the first
+    /// one that fails.
+    fn insert_from_files<'a>(
+        &mut self,
+        paths: impl IntoIterator<Item = &'a Path>,
+    ) -> Result<(), ParseFileError> {
+        for path in paths {
+            self.insert_from_file(path)
+                .map_err(|err| ParseFileError::InWordFile {
+                    path: path.to_string_lossy().to_string(),
+                    err: Box::new(err),
+                })?;
         }
 
+        Ok(())
+    }
+
+    /// Creates a Trie from a words file whose words can only contain the letters of the
+    /// given [`Alphabet`].
+    pub fn new_from_file_with_alphabet(
+        path: &Path,
+        alphabet: Alphabet,
+    ) -> Result<Self, ParseFileError> {
+        let mut word_trie = WordTrie::with_alphabet(alphabet);
+        word_trie.insert_from_file(path)?;
+
+        Ok(word_trie)
+    }
+
+    /// Inserts the words from a words file into the Trie.
+    ///
+    /// The words can only contain the letters of the Trie's [`Alphabet`] and its
+    /// allowed punctuation.
+    pub fn insert_from_file(&mut self, path: &Path) -> Result<(), ParseFileError> {
+        let words = FileReader::parse_word_file(path, |ch| self.is_valid_char(ch))?;
+        self.insert_all(words);
+
+        Ok(())
+    }
+
+    /// Creates a Trie from words read line by line from any reader, such as stdin or an
+    /// in-memory buffer.
+    pub fn new_from_reader(reader: impl BufRead) -> Result<Self, ParseFileError> {
+        let mut word_trie = WordTrie::default();
+        word_trie.insert_from_reader(reader)?;
+
+        Ok(word_trie)
+    }
+
+    /// Same as [`WordTrie::insert_from_file`] but reads the words from any reader.
+    pub fn insert_from_reader(&mut self, reader: impl BufRead) -> Result<(), ParseFileError> {
+        let words = FileReader::from_reader(reader).parse_words(|ch| self.is_valid_char(ch))?;
+        self.insert_all(words);
+
+        Ok(())
+    }
+
+    /// Writes every word of the Trie into a words file, one per line and sorted.
+    ///
+    /// Loading a words file and writing it back out removes its duplicate words and
+    /// lowercases them, unless the Trie preserves case.
+    pub fn write_to_file(&self, path: &Path) -> std::io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        let mut writer = std::io::BufWriter::new(file);
+        for word in self.iter() {
+            writeln!(writer, "{word}")?;
+        }
+
+        writer.flush()
+    }
+
+    /// Saves the Trie into a binary file so that it can be loaded faster than parsing
+    /// the words file with [`WordTrie::load_from_file`].
+    #[cfg(feature = "serde")]
+    pub fn save_to_file(&self, path: &Path) -> Result<(), TrieCacheError> {
+        let file = std::fs::File::create(path).map_err(|err| TrieCacheError::File {
+            path: path.to_string_lossy().to_string(),
+            err,
+        })?;
+        let mut writer = std::io::BufWriter::new(file);
+        bincode::serde::encode_into_std_write(self, &mut writer, bincode::config::standard())?;
+
+        Ok(())
+    }
+
+    /// Loads a Trie that was saved using [`WordTrie::save_to_file`].
+    #[cfg(feature = "serde")]
+    pub fn load_from_file(path: &Path) -> Result<Self, TrieCacheError> {
+        let file = std::fs::File::open(path).map_err(|err| TrieCacheError::File {
+            path: path.to_string_lossy().to_string(),
+            err,
+        })?;
+        let mut reader = std::io::BufReader::new(file);
+        let word_trie =
+            bincode::serde::decode_from_std_read(&mut reader, bincode::config::standard())?;
+
         Ok(word_trie)
     }
 }
 
 impl ScoredWordTrie {
     pub fn new_from_files(words_path: &Path, scores_path: &Path) -> Result<Self, ParseFileError> {
+        Self::new_from_files_with_alphabet(words_path, scores_path, Alphabet::Ascii)
+    }
+
+    /// Creates a scored Trie from a words file where every ASCII letter is worth 1 point.
+    pub fn new_from_words_only(words_path: &Path) -> Result<Self, ParseFileError> {
+        let word_trie = WordTrie::new_from_file(words_path)?;
+        let score_map = ('a'..='z').map(|ch| (ch, 1)).collect();
+
+        Ok(Self {
+            word_trie,
+            score_map,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a scored Trie from a words file using the English Scrabble letter scores.
+    pub fn with_scrabble_scores(words_path: &Path) -> Result<Self, ParseFileError> {
         let word_trie = WordTrie::new_from_file(words_path)?;
-        let score_map = FileReader::parse_scores_file(scores_path)?;
+        let score_map = HashMap::from(SCRABBLE_SCORES);
 
         Ok(Self {
             word_trie,
             score_map,
+            ..Default::default()
+        })
+    }
+
+    /// Creates a scored Trie from a words file and a scores file whose letters can only
+    /// be from the given [`Alphabet`].
+    pub fn new_from_files_with_alphabet(
+        words_path: &Path,
+        scores_path: &Path,
+        alphabet: Alphabet,
+    ) -> Result<Self, ParseFileError> {
+        let word_trie = WordTrie::new_from_file_with_alphabet(words_path, alphabet)?;
+        let score_map = FileReader::parse_scores_file(scores_path, alphabet)?;
+
+        Ok(Self {
+            word_trie,
+            score_map,
+            ..Default::default()
         })
     }
 }
@@ -40,27 +185,80 @@ pub enum ParseFileError {
     ParseWordFile(#[from] ParseWordFileError),
     #[error(transparent)]
     ParseScoreFile(#[from] ParseScoreFileError),
+    #[error("failed to load `{path}`: {err}")]
+    InWordFile {
+        path: String,
+        #[source]
+        err: Box<ParseFileError>,
+    },
 }
 
 #[derive(Debug, Error)]
-#[error("failed to load word file from `{path}`: {err}")]
+pub enum BuildError {
+    #[error("no words file was given")]
+    MissingWords,
+    #[error(transparent)]
+    ParseFile(#[from] ParseFileError),
+}
+
+#[derive(Debug, Error)]
+#[error("failed to open `{path}`: {err}")]
 pub struct OpenFileError {
     path: String,
     err: std::io::Error,
 }
 
+#[derive(Debug, Error)]
+#[error("failed to read line {line} of `{path}`: {err}")]
+pub struct ReadFileError {
+    path: String,
+    line: usize,
+    err: std::io::Error,
+}
+
+impl ReadFileError {
+    fn new(path: &str, line: usize, err: std::io::Error) -> Self {
+        Self {
+            path: path.to_string(),
+            line,
+            err,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+#[derive(Debug, Error)]
+pub enum TrieCacheError {
+    #[error("failed to access the trie file `{path}`: {err}")]
+    File { path: String, err: std::io::Error },
+    #[error("failed to save the trie: {0}")]
+    Encode(#[from] bincode::error::EncodeError),
+    #[error("failed to load the trie: {0}")]
+    Decode(#[from] bincode::error::DecodeError),
+}
+
+#[derive(Debug, Error)]
+#[error("Invalid word: \"{0}\". Words can only contain letters and the allowed punctuation.")]
+pub struct InvalidWordError(pub String);
+
 #[derive(Debug, Error)]
 pub enum ParseWordFileError {
     #[error(transparent)]
     OpenFile(#[from] OpenFileError),
-    #[error("Invalid word: \"{0}\". Words can only contain characters between a-z or A-Z.")]
-    InvalidWord(String),
+    #[error(transparent)]
+    ReadFile(#[from] ReadFileError),
+    #[error(
+        "Invalid word on line {0}: \"{1}\". Words can only contain letters and the allowed punctuation."
+    )]
+    InvalidWord(usize, String),
 }
 
 #[derive(Debug, Error)]
 pub enum ParseScoreFileError {
     #[error(transparent)]
     OpenFile(#[from] OpenFileError),
+    #[error(transparent)]
+    ReadFile(#[from] ReadFileError),
     #[error("Line {0} is missing an equal sign `=`: {1}")]
     MissingEqualSign(usize, String),
     #[error("The left side of the equal sign `=` must be a single character, got: {0}.")]
@@ -69,4 +267,159 @@ pub enum ParseScoreFileError {
         "The right side of the equal sign `=` must be a valid score but got `{0}`: error: {1}"
     )]
     InvalidScore(String, ParseIntError),
+    #[error("Line {0} redefines the score of `{1}`")]
+    DuplicateChar(usize, char),
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_query_accented_words() {
+        let trie = ScoredWordTrie::new_from_files_with_alphabet(
+            Path::new("./test_data/accented_words.txt"),
+            Path::new("./test_data/accented_scores.txt"),
+            Alphabet::Unicode,
+        )
+        .expect("should load the accented files");
+
+        assert_eq!(trie.word_trie.get_words_sorted("ÉFACÉ"), ["café"]);
+        assert_eq!(trie.word_trie.get_words_sorted("nñoian"), ["nina", "niño"]);
+        assert_eq!(trie.word_trie.get_words_sorted("ététh"), ["thé", "été"]);
+        assert_eq!(
+            trie.get_words("cafénñoi"),
+            [("niño".to_string(), 11), ("café".to_string(), 10)]
+        );
+    }
+
+    #[test]
+    fn test_write_to_file() {
+        let dir = std::env::temp_dir();
+        let pid = std::process::id();
+        let input_path = dir.join(format!("lexicon_test_write_to_file_input_{pid}.txt"));
+        let output_path = dir.join(format!("lexicon_test_write_to_file_output_{pid}.txt"));
+        std::fs::write(&input_path, "radar\nDart\nrad\ndart\nt-shirt\nradar\n")
+            .expect("write the input file");
+
+        let trie = WordTrie::new_from_file(&input_path).expect("load the input file");
+        trie.write_to_file(&output_path)
+            .expect("write the output file");
+        let output = std::fs::read_to_string(&output_path).expect("read the output file");
+        std::fs::remove_file(&input_path).expect("remove the input file");
+        std::fs::remove_file(&output_path).expect("remove the output file");
+
+        assert_eq!(output, "dart\nrad\nradar\nt-shirt\n");
+    }
+
+    #[test]
+    fn test_new_from_words_only() {
+        let trie = ScoredWordTrie::new_from_words_only(Path::new("./test_data/words.txt"))
+            .expect("should load the words file");
+
+        let words = trie.get_words("radartc");
+        assert!(!words.is_empty());
+        for (word, score) in words {
+            assert_eq!(score as usize, word.len(), "{word}");
+        }
+    }
+
+    #[test]
+    fn test_with_scrabble_scores() {
+        let trie = ScoredWordTrie::with_scrabble_scores(Path::new("./test_data/words.txt"))
+            .expect("should load the words file");
+
+        let words = trie.get_words("quiz");
+        assert_eq!(words.first(), Some(&("quiz".to_string(), 22)));
+
+        let words = trie.get_words("jukebox");
+        assert_eq!(words.first(), Some(&("jukebox".to_string(), 27)));
+    }
+
+    #[test]
+    fn test_load_punctuated_words() {
+        let trie = WordTrie::new_from_file(Path::new("./test_data/punctuated_words.txt"))
+            .expect("should load the punctuated words");
+
+        assert_eq!(trie.get_words_sorted("dont"), ["don't", "dont"]);
+        assert_eq!(trie.get_words_sorted("inlawmother"), ["mother-in-law"]);
+        assert_eq!(trie.get_words_sorted("tac"), ["cat"]);
+    }
+
+    #[test]
+    fn test_punctuation_can_be_disallowed() {
+        let mut trie = WordTrie::default().with_punctuation(&[]);
+        let result = trie.insert_from_file(Path::new("./test_data/punctuated_words.txt"));
+
+        assert!(matches!(
+            result,
+            Err(ParseFileError::ParseWordFile(ParseWordFileError::InvalidWord(2, word))) if word == "don't"
+        ));
+    }
+
+    #[test]
+    fn test_new_from_reader() {
+        let input = b"cat\nact\ndog\n";
+
+        let trie = WordTrie::new_from_reader(&input[..]).expect("should read the words");
+
+        assert_eq!(trie.get_words_sorted("tac"), ["act", "cat"]);
+        assert_eq!(trie.get_words_sorted("god"), ["dog"]);
+    }
+
+    #[test]
+    fn test_new_from_files() {
+        let trie = WordTrie::new_from_files(&[
+            Path::new("./test_data/words.txt"),
+            Path::new("./test_data/custom_words.txt"),
+        ])
+        .expect("should load both files");
+
+        assert_eq!(trie.get_words_sorted("jukebox"), ["jukebox"]);
+        assert_eq!(trie.get_words_sorted("tac"), ["cat", "tac"]);
+        assert_eq!(trie.get_words_sorted("radar"), ["rad", "radar"]);
+
+        let Err(err) = WordTrie::new_from_files(&[
+            Path::new("./test_data/words.txt"),
+            Path::new("./test_data/invalid_words.txt"),
+        ]) else {
+            panic!("digits are not allowed");
+        };
+        assert!(err
+            .to_string()
+            .starts_with("failed to load `./test_data/invalid_words.txt`: "));
+    }
+
+    #[test]
+    fn test_missing_words_file() {
+        let result = WordTrie::new_from_file(Path::new("./test_data/missing.txt"));
+
+        let Err(err) = result else {
+            panic!("loading a missing file should fail");
+        };
+        assert!(matches!(
+            err,
+            ParseFileError::ParseWordFile(ParseWordFileError::OpenFile(_))
+        ));
+        assert!(err.to_string().contains("./test_data/missing.txt"));
+
+        let result = ScoredWordTrie::new_from_files(
+            Path::new("./test_data/missing.txt"),
+            Path::new("./test_data/commented_scores.txt"),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_ascii_rejects_accented_words() {
+        let result = WordTrie::new_from_file(Path::new("./test_data/accented_words.txt"));
+
+        assert!(matches!(
+            result,
+            Err(ParseFileError::ParseWordFile(
+                ParseWordFileError::InvalidWord(1, _)
+            ))
+        ));
+    }
 }
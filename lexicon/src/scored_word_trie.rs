@@ -1,60 +1,721 @@
-use super::word_trie::WordTrie;
+mod builder;
+
+use super::word_trie::{FoundWord, Path, SearchSession, Tile, WordTrie};
+pub use builder::ScoredWordTrieBuilder;
+use regex::Regex;
 use std::cmp;
-use std::collections::HashMap;
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
 
 /// A wrapper over [`WordTrie`] that returns the words together with their scores.
 #[derive(Default)]
 pub struct ScoredWordTrie {
     pub word_trie: WordTrie,
-    pub score_map: HashMap<char, u8>,
+    /// The score of each letter, which can be zero or negative for penalty tiles.
+    pub score_map: HashMap<char, i32>,
+    /// Other named score maps that can replace the [`score_map`] with
+    /// [`ScoredWordTrie::select_score_set`].
+    ///
+    /// [`score_map`]: ScoredWordTrie::score_map
+    pub score_sets: BTreeMap<String, HashMap<char, i32>>,
+    /// The score of letters missing from the [`score_map`].
+    ///
+    /// [`score_map`]: ScoredWordTrie::score_map
+    pub default_score: i32,
+    pub ordering: ResultOrdering,
+    /// The bonus added to the score of words that use up the whole rack.
+    pub bingo_bonus: u32,
+    /// The number of letters a word needs to get the [`bingo_bonus`].
+    ///
+    /// [`bingo_bonus`]: ScoredWordTrie::bingo_bonus
+    pub rack_size: usize,
+}
+
+/// The point values of the letters in English Scrabble.
+pub const SCRABBLE_SCORES: [(char, i32); 26] = [
+    ('a', 1),
+    ('b', 3),
+    ('c', 3),
+    ('d', 2),
+    ('e', 1),
+    ('f', 4),
+    ('g', 2),
+    ('h', 4),
+    ('i', 1),
+    ('j', 8),
+    ('k', 5),
+    ('l', 1),
+    ('m', 3),
+    ('n', 1),
+    ('o', 1),
+    ('p', 3),
+    ('q', 10),
+    ('r', 1),
+    ('s', 1),
+    ('t', 1),
+    ('u', 1),
+    ('v', 4),
+    ('w', 4),
+    ('x', 8),
+    ('y', 4),
+    ('z', 10),
+];
+
+/// The point values of the letters in Words With Friends.
+pub const WORDS_WITH_FRIENDS_SCORES: [(char, i32); 26] = [
+    ('a', 1),
+    ('b', 4),
+    ('c', 4),
+    ('d', 2),
+    ('e', 1),
+    ('f', 4),
+    ('g', 3),
+    ('h', 3),
+    ('i', 1),
+    ('j', 10),
+    ('k', 5),
+    ('l', 2),
+    ('m', 4),
+    ('n', 2),
+    ('o', 1),
+    ('p', 4),
+    ('q', 10),
+    ('r', 1),
+    ('s', 1),
+    ('t', 1),
+    ('u', 2),
+    ('v', 5),
+    ('w', 4),
+    ('x', 8),
+    ('y', 3),
+    ('z', 10),
+];
+
+/// The letter and word multipliers of the board square a letter is placed on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TileMultiplier {
+    /// Multiplies the score of the letter placed on the square.
+    pub letter: u32,
+    /// Multiplies the score of the whole word after the letter scores are summed.
+    pub word: u32,
+}
+
+impl TileMultiplier {
+    /// A square without any multipliers.
+    pub const NONE: Self = Self { letter: 1, word: 1 };
+
+    /// A square that multiplies the score of the letter placed on it.
+    pub fn letter(multiplier: u32) -> Self {
+        Self {
+            letter: multiplier,
+            ..Self::NONE
+        }
+    }
+
+    /// A square that multiplies the score of the whole word.
+    pub fn word(multiplier: u32) -> Self {
+        Self {
+            word: multiplier,
+            ..Self::NONE
+        }
+    }
+}
+
+impl Default for TileMultiplier {
+    fn default() -> Self {
+        Self::NONE
+    }
+}
+
+/// How the scored results are ordered.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ResultOrdering {
+    /// Orders the results by:
+    ///
+    /// 1. score, highest first
+    /// 2. number of wildcards `*` used, fewest first
+    /// 3. length, longest first
+    /// 4. alphabetically
+    #[default]
+    TieBreak,
+    /// Orders the results by score only, highest first. The order of words with
+    /// equal scores is unspecified.
+    ScoreOnly,
+    /// Orders the results alphabetically, ignoring their scores.
+    Alphabetical,
 }
 
 impl ScoredWordTrie {
+    /// Adds the `bonus` to the score of words that are exactly `rack_size` letters long.
+    ///
+    /// This can be used for the 50 points bonus that Scrabble awards for using all seven
+    /// tiles.
+    pub fn with_bingo_bonus(mut self, bonus: u32, rack_size: usize) -> Self {
+        self.bingo_bonus = bonus;
+        self.rack_size = rack_size;
+        self
+    }
+
+    /// Adds a named score map that can later be used with
+    /// [`ScoredWordTrie::select_score_set`], replacing any set with the same name.
+    pub fn add_score_set(&mut self, name: &str, score_map: HashMap<char, i32>) {
+        self.score_sets.insert(name.to_string(), score_map);
+    }
+
+    /// Scores the words using the named score map from now on, returning `false` if
+    /// there is no score set with that name.
+    pub fn select_score_set(&mut self, name: &str) -> bool {
+        let Some(score_map) = self.score_sets.get(name) else {
+            return false;
+        };
+
+        self.score_map = score_map.clone();
+        true
+    }
+
+    /// Returns the names of the score sets, in alphabetical order.
+    pub fn score_set_names(&self) -> impl Iterator<Item = &str> {
+        self.score_sets.keys().map(String::as_str)
+    }
+
     /// Gets all the words that could be built using the given letters sorted by score.
-    pub fn get_words(&self, letters: &str) -> Vec<(String, u8)> {
-        let words = self.word_trie.get_words(letters);
-        let mut words_with_score = words
-            .into_iter()
-            .map(|word| {
-                let score = self.calculate_score(&word);
-                (word, score)
-            })
-            .collect::<Vec<_>>();
+    ///
+    /// Letters filled using a wildcard `*` score zero.
+    pub fn get_words(&self, letters: &str) -> Vec<(String, u32)> {
+        let words = self.word_trie.iter_found_words(letters).collect_found();
+        self.score_words(words.into_iter(), &[])
+    }
 
-        words_with_score.sort_by_key(|(_word, score)| cmp::Reverse(*score));
+    /// Gets the `limit` highest-scoring words that could be built using the given
+    /// letters, the same as the first `limit` words of [`ScoredWordTrie::get_words`].
+    ///
+    /// With [`ResultOrdering::ScoreOnly`] only the scores are the same, since words with
+    /// equal scores can come in any order and so different ones can make the cut.
+    ///
+    /// The branches that could lead to the highest scores are searched first so the
+    /// search stops as soon as no branch left could beat the words already found.
+    pub fn get_words_limited(&self, letters: &str, limit: usize) -> Vec<(String, u32)> {
+        if limit == 0 {
+            return Vec::new();
+        }
 
-        words_with_score
+        // Alphabetical results don't depend on the scores so there is nothing to prune
+        if self.ordering == ResultOrdering::Alphabetical {
+            let mut words = self.get_words(letters);
+            words.truncate(limit);
+            return words;
+        }
+
+        let mut words = self
+            .word_trie
+            .iter_found_words_best_first(letters, |path| self.score_bound(path));
+        let mut found = Vec::new();
+        // The `limit` highest scores found so far with the lowest on top
+        let mut top_scores = BinaryHeap::with_capacity(limit + 1);
+
+        loop {
+            // Words scoring the same as the lowest top score are still searched so that
+            // ties are broken the same way as in a full search
+            if let Some(cmp::Reverse(lowest)) = top_scores.peek() {
+                if top_scores.len() == limit
+                    && words.peek_bound().is_none_or(|bound| bound < *lowest)
+                {
+                    break;
+                }
+            }
+
+            let Some(word) = words.next() else {
+                break;
+            };
+
+            top_scores.push(cmp::Reverse(self.calculate_score(&word, &[])));
+            if top_scores.len() > limit {
+                top_scores.pop();
+            }
+            found.push(word);
+        }
+
+        let mut words = self.score_words(found.into_iter(), &[]);
+        words.truncate(limit);
+        words
+    }
+
+    /// Gets all the words that could be built using the given letters sorted by score,
+    /// then alphabetically.
+    ///
+    /// Unlike [`ScoredWordTrie::get_words`], this ignores the configured
+    /// [`ResultOrdering`] so the order of words with equal scores is always the same.
+    pub fn get_words_scored_sorted(&self, letters: &str) -> Vec<(String, u32)> {
+        let mut words = self.get_words(letters);
+        words.sort_by(|(a, a_score), (b, b_score)| b_score.cmp(a_score).then_with(|| a.cmp(b)));
+        words
     }
 
     /// Gets all the words that matches the given regular expression sorted by score.
+    ///
+    /// Letters filled using a wildcard `*` score zero. If `letters` is empty, the whole
+    /// dictionary is searched for matches instead.
     pub fn get_word_matches(
         &self,
         letters: &str,
         expr: &str,
-    ) -> Result<Vec<(String, u8)>, regex::Error> {
-        let words = self.word_trie.get_word_matches(letters, expr)?;
-        let mut words_with_score = words
+    ) -> Result<Vec<(String, u32)>, regex::Error> {
+        let re = Regex::new(expr)?;
+        Ok(self.get_word_matches_compiled(letters, &re))
+    }
+
+    /// Gets all the words that matches the given compiled regular expression sorted by
+    /// score.
+    ///
+    /// Letters filled using a wildcard `*` score zero.
+    pub fn get_word_matches_compiled(&self, letters: &str, re: &Regex) -> Vec<(String, u32)> {
+        let mut words = Vec::new();
+        self.word_trie
+            .iter_match_candidates(letters, re)
+            .search(|found| {
+                if re.is_match(&found.word) {
+                    words.push(found);
+                }
+            });
+        self.score_words(words.into_iter(), &[])
+    }
+
+    /// Gets all the words that could be built using the given letters sorted by score,
+    /// together with the rack tiles each word uses up.
+    ///
+    /// The tiles tell apart the letters that were filled using a wildcard `*`.
+    pub fn get_words_with_tiles(&self, letters: &str) -> Vec<(String, u32, Vec<Tile>)> {
+        let words = self.word_trie.iter_found_words(letters).collect_found();
+        let punctuation = self.word_trie.punctuation();
+
+        self.rank_words(words.into_iter(), &[])
             .into_iter()
-            .map(|word| {
-                let score = self.calculate_score(&word);
-                (word, score)
+            .map(|(found, score)| {
+                let tiles = found.tiles(punctuation);
+                (self.word_trie.display_word(found.word), score, tiles)
             })
+            .collect()
+    }
+
+    /// Same as [`ScoredWordTrie::get_words`] but abandons the search and returns `None`
+    /// once the `cancelled` flag is set to `true`.
+    ///
+    /// This lets a search that became obsolete, like when the user keeps typing, stop
+    /// early instead of running to completion.
+    pub fn get_words_cancellable(
+        &self,
+        letters: &str,
+        cancelled: &AtomicBool,
+    ) -> Option<Vec<(String, u32)>> {
+        let words = self
+            .word_trie
+            .iter_found_words(letters)
+            .cancellable(cancelled)
+            .collect_found();
+        let words = self.score_words(words.into_iter(), &[]);
+        (!cancelled.load(Ordering::Relaxed)).then_some(words)
+    }
+
+    /// Same as [`ScoredWordTrie::get_words_cancellable`] but reuses the previous search
+    /// of the [`SearchSession`] when letters were only appended to the rack.
+    pub fn get_words_in_session(
+        &self,
+        session: &mut SearchSession,
+        letters: &str,
+        cancelled: &AtomicBool,
+    ) -> Option<Vec<(String, u32)>> {
+        self.get_words_in_session_with_progress(session, letters, cancelled, Duration::MAX, |_| {})
+    }
+
+    /// Same as [`ScoredWordTrie::get_words_in_session`] but also passes the sorted words
+    /// found so far to `on_progress` about every `interval` while searching.
+    pub fn get_words_in_session_with_progress(
+        &self,
+        session: &mut SearchSession,
+        letters: &str,
+        cancelled: &AtomicBool,
+        interval: Duration,
+        mut on_progress: impl FnMut(Vec<(String, u32)>),
+    ) -> Option<Vec<(String, u32)>> {
+        let words = session.search_with_progress(
+            &self.word_trie,
+            letters,
+            cancelled,
+            interval,
+            &mut |words| on_progress(self.score_words(words.iter().cloned(), &[])),
+        )?;
+
+        Some(self.score_words(words.iter().cloned(), &[]))
+    }
+
+    /// Same as [`ScoredWordTrie::get_word_matches_compiled`] but abandons the search and
+    /// returns `None` once the `cancelled` flag is set to `true`.
+    pub fn get_word_matches_cancellable(
+        &self,
+        letters: &str,
+        re: &Regex,
+        cancelled: &AtomicBool,
+    ) -> Option<Vec<(String, u32)>> {
+        self.get_word_matches_with_progress(letters, re, cancelled, Duration::MAX, |_| {})
+    }
+
+    /// Same as [`ScoredWordTrie::get_word_matches_cancellable`] but also passes the
+    /// sorted words found so far to `on_progress` about every `interval` while
+    /// searching.
+    pub fn get_word_matches_with_progress(
+        &self,
+        letters: &str,
+        re: &Regex,
+        cancelled: &AtomicBool,
+        interval: Duration,
+        mut on_progress: impl FnMut(Vec<(String, u32)>),
+    ) -> Option<Vec<(String, u32)>> {
+        let mut words = Vec::new();
+        let mut last_report = Instant::now();
+
+        self.word_trie
+            .iter_match_candidates(letters, re)
+            .cancellable(cancelled)
+            .search(|found| {
+                if !re.is_match(&found.word) {
+                    return;
+                }
+                words.push(found);
+
+                if last_report.elapsed() >= interval {
+                    on_progress(self.score_words(words.iter().cloned(), &[]));
+                    last_report = Instant::now();
+                }
+            });
+
+        if cancelled.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        Some(self.score_words(words.into_iter(), &[]))
+    }
+
+    /// Gets all the words that could be built using the given letters sorted by their
+    /// score when placed on board squares with the given multipliers.
+    ///
+    /// The `n`th letter of a word is placed on the square of the `n`th multiplier.
+    /// Letters past the last multiplier are placed on squares without multipliers.
+    ///
+    /// A wildcard `*` can stand in for any occurrence of the letter it fills, so it is
+    /// placed on the square where it lowers the score the least.
+    pub fn get_words_scored_on_board(
+        &self,
+        letters: &str,
+        multipliers: &[TileMultiplier],
+    ) -> Vec<(String, u32)> {
+        let words = self.word_trie.iter_found_words(letters).collect_found();
+        self.score_words(words.into_iter(), multipliers)
+    }
+
+    /// Calculates the score of a word built only from letter tiles, including the bingo
+    /// bonus, without searching the Trie.
+    ///
+    /// The word doesn't need to be in the Trie.
+    pub fn score_word(&self, word: &str) -> u32 {
+        let found = FoundWord {
+            word: word.to_lowercase(),
+            wildcards: Vec::new(),
+        };
+        self.calculate_score(&found, &[])
+    }
+
+    /// Returns the rack letters left over after building the word from them, in the
+    /// order they are in the rack, or `None` if the word can't be built from them.
+    ///
+    /// Like when searching, a wildcard `*` is only used for a letter once all of its
+    /// tiles are used up, and punctuation doesn't use up any letters.
+    pub fn remaining_after(&self, letters: &str, word: &str) -> Option<String> {
+        let alphabet = self.word_trie.alphabet();
+        let rack = letters
+            .chars()
+            .filter(|ch| alphabet.is_letter(*ch) || *ch == '*')
+            .flat_map(char::to_lowercase)
             .collect::<Vec<_>>();
+        let mut remaining = rack.iter().fold(HashMap::new(), |mut acc, ch| {
+            *acc.entry(*ch).or_insert(0usize) += 1;
+            acc
+        });
+
+        for ch in word.to_lowercase().chars() {
+            if self.word_trie.punctuation().contains(&ch) {
+                continue;
+            }
+
+            let tile = if remaining.get(&ch).is_some_and(|count| *count > 0) {
+                ch
+            } else {
+                '*'
+            };
+            let count = remaining.get_mut(&tile).filter(|count| **count > 0)?;
+            *count -= 1;
+        }
+
+        let leftover = rack
+            .into_iter()
+            .filter(|ch| {
+                let count = remaining.get_mut(ch).expect("every rack letter is counted");
+                let keep = *count > 0;
+                *count = count.saturating_sub(1);
+                keep
+            })
+            .collect();
+
+        Some(leftover)
+    }
+
+    /// Gets the highest-scoring word that could be built using the given letters.
+    ///
+    /// Words with equal scores are broken like [`ResultOrdering::TieBreak`], so this is
+    /// the first word of [`ScoredWordTrie::get_words`] with the default ordering. Only
+    /// the best word so far is kept during the search instead of collecting and sorting
+    /// every word.
+    pub fn best_word(&self, letters: &str) -> Option<(String, u32)> {
+        let punctuation = self.word_trie.punctuation();
+        let mut best: Option<(FoundWord, u32)> = None;
+
+        self.word_trie.iter_found_words(letters).search(|found| {
+            let score = self.calculate_score(&found, &[]);
+            let is_better = best.as_ref().is_none_or(|(best_found, best_score)| {
+                tie_break((&found, score), (best_found, *best_score), punctuation).is_lt()
+            });
+            if is_better {
+                best = Some((found, score));
+            }
+        });
+
+        best.map(|(found, score)| (self.word_trie.display_word(found.word), score))
+    }
+
+    /// Gets the highest-scoring word that could be built using the given letters for
+    /// each starting letter.
+    ///
+    /// Words with equal scores are broken like [`ResultOrdering::TieBreak`], so the word
+    /// of each letter is the first word starting with it in
+    /// [`ScoredWordTrie::get_words`] with the default ordering.
+    pub fn best_per_starting_letter(&self, rack: &str) -> BTreeMap<char, (String, u32)> {
+        let punctuation = self.word_trie.punctuation();
+        let mut best = BTreeMap::<char, (FoundWord, u32)>::new();
 
-        words_with_score.sort_by_key(|(_word, score)| cmp::Reverse(*score));
+        self.word_trie.iter_found_words(rack).search(|found| {
+            let Some(first_ch) = found.word.chars().next() else {
+                return;
+            };
+            let score = self.calculate_score(&found, &[]);
 
-        Ok(words_with_score)
+            match best.entry(first_ch) {
+                Entry::Vacant(entry) => {
+                    entry.insert((found, score));
+                }
+                Entry::Occupied(mut entry) => {
+                    let (best_found, best_score) = entry.get();
+                    if tie_break((&found, score), (best_found, *best_score), punctuation).is_lt() {
+                        entry.insert((found, score));
+                    }
+                }
+            }
+        });
+
+        best.into_iter()
+            .map(|(ch, (found, score))| (ch, (self.word_trie.display_word(found.word), score)))
+            .collect()
     }
 
-    fn calculate_score(&self, word: &str) -> u8 {
-        word.chars().filter_map(|ch| self.score_map.get(&ch)).sum()
+    /// Scores the found words then sorts them according to the configured
+    /// [`ResultOrdering`].
+    fn score_words(
+        &self,
+        words: impl Iterator<Item = FoundWord>,
+        multipliers: &[TileMultiplier],
+    ) -> Vec<(String, u32)> {
+        self.rank_words(words, multipliers)
+            .into_iter()
+            .map(|(found, score)| (self.word_trie.display_word(found.word), score))
+            .collect()
     }
+
+    /// Same as [`ScoredWordTrie::score_words`] but keeps the whole [`FoundWord`].
+    fn rank_words(
+        &self,
+        words: impl Iterator<Item = FoundWord>,
+        multipliers: &[TileMultiplier],
+    ) -> Vec<(FoundWord, u32)> {
+        let mut words_with_score = words
+            .map(|found| {
+                let score = self.calculate_score(&found, multipliers);
+                (found, score)
+            })
+            .collect::<Vec<_>>();
+
+        match self.ordering {
+            ResultOrdering::TieBreak => {
+                let punctuation = self.word_trie.punctuation();
+                words_with_score.sort_by(|(a, a_score), (b, b_score)| {
+                    tie_break((a, *a_score), (b, *b_score), punctuation)
+                });
+            }
+            ResultOrdering::ScoreOnly => {
+                words_with_score.sort_by_key(|(_found, score)| cmp::Reverse(*score));
+            }
+            ResultOrdering::Alphabetical => {
+                words_with_score.sort_by(|(a, _), (b, _)| a.word.cmp(&b.word));
+            }
+        }
+
+        words_with_score
+    }
+
+    /// Returns the highest score any word reachable from the path could have: the score
+    /// of its letters, plus the score of every letter still held, plus the bingo bonus
+    /// if the word isn't longer than the rack yet.
+    fn score_bound(&self, path: &Path) -> u32 {
+        let punctuation = self.word_trie.punctuation();
+
+        let placed = path
+            .word_buf
+            .chars()
+            .enumerate()
+            .filter(|(i, ch)| !path.wildcards.contains(i) && !punctuation.contains(ch))
+            .map(|(_, ch)| i64::from(self.letter_score(ch)))
+            .fold(0i64, i64::saturating_add);
+        // Held letters with negative scores don't need to be used
+        let held = path
+            .remaining_letters
+            .iter()
+            .filter(|(ch, _)| *ch != '*')
+            .map(|(ch, count)| {
+                let count = i64::try_from(count).unwrap_or(i64::MAX);
+                i64::from(self.letter_score(ch).max(0)).saturating_mul(count)
+            })
+            .fold(0i64, i64::saturating_add);
+
+        let bound = clamp_score(placed.saturating_add(held));
+        if tile_count(&path.word_buf, punctuation) <= self.rack_size {
+            bound.saturating_add(self.bingo_bonus)
+        } else {
+            bound
+        }
+    }
+
+    fn letter_score(&self, ch: char) -> i32 {
+        self.score_map
+            .get(&ch)
+            .copied()
+            .unwrap_or(self.default_score)
+    }
+
+    /// Calculates the score of the word by applying the letter multipliers before
+    /// summing the letter scores, then applying the word multipliers to the total.
+    ///
+    /// Letters filled using a wildcard `*` and punctuation score zero but still apply the
+    /// word multiplier of their square. Letters with negative scores lower the total
+    /// but a word never scores below zero.
+    fn calculate_score(&self, found: &FoundWord, multipliers: &[TileMultiplier]) -> u32 {
+        let punctuation = self.word_trie.punctuation();
+        let wildcards = self.place_wildcards(found, multipliers);
+        let mut word_multiplier = 1i64;
+
+        let score = found
+            .word
+            .chars()
+            .enumerate()
+            .map(|(i, ch)| {
+                let multiplier = multipliers.get(i).copied().unwrap_or_default();
+                word_multiplier = word_multiplier.saturating_mul(multiplier.word.into());
+
+                if wildcards.contains(&i) || punctuation.contains(&ch) {
+                    return 0;
+                }
+
+                i64::from(self.letter_score(ch)).saturating_mul(multiplier.letter.into())
+            })
+            .fold(0i64, i64::saturating_add)
+            .saturating_mul(word_multiplier);
+        let score = clamp_score(score);
+
+        if tile_count(&found.word, punctuation) == self.rack_size {
+            score.saturating_add(self.bingo_bonus)
+        } else {
+            score
+        }
+    }
+
+    /// Returns the positions of the wildcards of the found word that score the most on
+    /// the board.
+    ///
+    /// The search uses the tiles of a letter before the wildcards, but a wildcard and a
+    /// tile of the same letter can trade places. So the wildcards filling a letter are
+    /// moved to the occurrences of the letter that would score the least with a tile.
+    fn place_wildcards(&self, found: &FoundWord, multipliers: &[TileMultiplier]) -> Vec<usize> {
+        // Without multipliers every occurrence of a letter scores the same
+        if multipliers.is_empty() || found.wildcards.is_empty() {
+            return found.wildcards.clone();
+        }
+
+        let chars = found.word.chars().collect::<Vec<_>>();
+        let mut filled = found
+            .wildcards
+            .iter()
+            .map(|i| chars[*i])
+            .collect::<Vec<_>>();
+        filled.sort_unstable();
+        filled.dedup();
+
+        let mut wildcards = Vec::with_capacity(found.wildcards.len());
+        for ch in filled {
+            let count = found.wildcards.iter().filter(|i| chars[**i] == ch).count();
+            let tile_score = |i: &usize| {
+                let multiplier = multipliers.get(*i).copied().unwrap_or_default();
+                i64::from(self.letter_score(ch)).saturating_mul(multiplier.letter.into())
+            };
+
+            let mut occurrences = (0..chars.len())
+                .filter(|i| chars[*i] == ch)
+                .collect::<Vec<_>>();
+            occurrences.sort_by_key(tile_score);
+            wildcards.extend(occurrences.into_iter().take(count));
+        }
+
+        wildcards.sort_unstable();
+        wildcards
+    }
+}
+
+/// Converts a signed total into a score, rounding negative totals up to zero.
+fn clamp_score(total: i64) -> u32 {
+    u32::try_from(total.max(0)).unwrap_or(u32::MAX)
+}
+
+/// Returns the number of rack tiles used to build the word, which is its length without
+/// the `punctuation`.
+fn tile_count(word: &str, punctuation: &[char]) -> usize {
+    word.chars().filter(|ch| !punctuation.contains(ch)).count()
+}
+
+/// Orders the scored words as described in [`ResultOrdering::TieBreak`].
+fn tie_break(
+    (a, a_score): (&FoundWord, u32),
+    (b, b_score): (&FoundWord, u32),
+    punctuation: &[char],
+) -> cmp::Ordering {
+    b_score
+        .cmp(&a_score)
+        .then_with(|| a.wildcards.len().cmp(&b.wildcards.len()))
+        .then_with(|| tile_count(&b.word, punctuation).cmp(&tile_count(&a.word, punctuation)))
+        .then_with(|| a.word.cmp(&b.word))
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
     use pretty_assertions::assert_eq;
+    use std::path::Path;
 
     #[test]
     pub fn test_get_words_with_score() {
@@ -64,6 +725,7 @@ mod test {
         let word_trie = ScoredWordTrie {
             word_trie,
             score_map: HashMap::from([('r', 1), ('t', 2), ('d', 3)]),
+            ..Default::default()
         };
 
         assert_eq!(
@@ -72,9 +734,9 @@ mod test {
                 .into_iter()
                 .collect::<Vec<_>>(),
             [
-                ("dart".to_string(), 6u8),
-                ("radar".to_string(), 5u8),
-                ("rad".to_string(), 4u8),
+                ("dart".to_string(), 6u32),
+                ("radar".to_string(), 5u32),
+                ("rad".to_string(), 4u32),
             ]
         );
     }
@@ -87,6 +749,7 @@ mod test {
         let word_trie = ScoredWordTrie {
             word_trie,
             score_map: HashMap::from([('r', 1), ('t', 2), ('d', 3)]),
+            ..Default::default()
         };
 
         assert_eq!(
@@ -95,7 +758,578 @@ mod test {
                 .expect("a valid regex")
                 .into_iter()
                 .collect::<Vec<_>>(),
-            [("radar".to_string(), 5u8), ("rad".to_string(), 4u8),]
+            [("radar".to_string(), 5u32), ("rad".to_string(), 4u32),]
+        );
+    }
+
+    #[test]
+    pub fn test_remaining_after() {
+        let word_trie = ScoredWordTrie::default();
+
+        assert_eq!(
+            word_trie.remaining_after("RaDaRtc", "dart"),
+            Some("rac".to_string())
+        );
+        assert_eq!(
+            word_trie.remaining_after("radar", "radar"),
+            Some(String::new())
+        );
+        assert_eq!(
+            word_trie.remaining_after("ab*e*", "babe"),
+            Some("*".to_string())
+        );
+        assert_eq!(
+            word_trie.remaining_after("dontx", "don't"),
+            Some("x".to_string())
+        );
+        assert_eq!(word_trie.remaining_after("rad", "radar"), None);
+        assert_eq!(word_trie.remaining_after("cat", "dog"), None);
+    }
+
+    #[test]
+    pub fn test_zero_and_negative_scores() {
+        let mut word_trie = WordTrie::default();
+        word_trie.insert_all(["at", "tax", "tab", "bat", "ax"]);
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('a', 1), ('t', 1), ('b', 0), ('x', -3)]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            word_trie.get_words("abtx"),
+            [
+                ("bat".to_string(), 2),
+                ("tab".to_string(), 2),
+                ("at".to_string(), 2),
+                ("tax".to_string(), 0),
+                ("ax".to_string(), 0),
+            ]
+        );
+        assert_eq!(word_trie.score_word("ax"), 0);
+        assert_eq!(
+            word_trie.get_words_limited("abtx", 2),
+            [("bat".to_string(), 2), ("tab".to_string(), 2)]
+        );
+    }
+
+    #[test]
+    pub fn test_score_sets() {
+        let mut word_trie =
+            ScoredWordTrie::with_scrabble_scores(Path::new("./test_data/words.txt"))
+                .expect("fixture loads");
+        word_trie.add_score_set("scrabble", HashMap::from(SCRABBLE_SCORES));
+        word_trie.add_score_set("wwf", HashMap::from(WORDS_WITH_FRIENDS_SCORES));
+
+        assert_eq!(
+            word_trie.score_set_names().collect::<Vec<_>>(),
+            ["scrabble", "wwf"]
+        );
+        assert_eq!(
+            word_trie.best_word("bjeoukx"),
+            Some(("jukebox".to_string(), 27))
+        );
+
+        assert!(word_trie.select_score_set("wwf"));
+        assert_eq!(
+            word_trie.best_word("bjeoukx"),
+            Some(("jukebox".to_string(), 31))
+        );
+
+        assert!(!word_trie.select_score_set("unknown"));
+        assert!(word_trie.select_score_set("scrabble"));
+        assert_eq!(word_trie.score_word("jukebox"), 27);
+    }
+
+    #[test]
+    pub fn test_score_word() {
+        let word_trie = ScoredWordTrie::with_scrabble_scores(Path::new("./test_data/words.txt"))
+            .expect("fixture loads")
+            .with_bingo_bonus(50, 7);
+
+        for (word, score) in word_trie.get_words("radicalt") {
+            assert_eq!(word_trie.score_word(&word), score, "{word}");
+        }
+        assert_eq!(word_trie.score_word("Jukebox"), 77);
+        assert_eq!(word_trie.score_word("zzz"), 30);
+    }
+
+    #[test]
+    pub fn test_best_word() {
+        let word_trie = ScoredWordTrie::with_scrabble_scores(Path::new("./test_data/words.txt"))
+            .expect("fixture loads");
+
+        for letters in ["radar", "radtic*", "bjeoukx", "zz", "*uiz", ""] {
+            assert_eq!(
+                word_trie.best_word(letters),
+                word_trie.get_words(letters).into_iter().next(),
+                "best word of {letters:?}"
+            );
+        }
+        assert_eq!(
+            word_trie.best_word("bjeoukx"),
+            Some(("jukebox".to_string(), 27))
+        );
+    }
+
+    #[test]
+    pub fn test_best_per_starting_letter() {
+        let mut word_trie = WordTrie::default();
+        let words = ["rad", "radar", "dart", "drat", "tad", "art", "tar"];
+        words.iter().for_each(|word| word_trie.insert(word));
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('a', 1), ('r', 1), ('t', 2), ('d', 3)]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            word_trie.best_per_starting_letter("radart"),
+            BTreeMap::from([
+                ('a', ("art".to_string(), 4)),
+                ('d', ("dart".to_string(), 7)),
+                ('r', ("radar".to_string(), 7)),
+                ('t', ("tad".to_string(), 6)),
+            ])
+        );
+    }
+
+    #[test]
+    pub fn test_best_per_starting_letter_breaks_ties_like_get_words() {
+        let mut word_trie = WordTrie::default();
+        word_trie.insert_all(["cab", "cat", "cats", "tac"]);
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('c', 3), ('a', 1), ('t', 0), ('b', 3)]),
+            ..Default::default()
+        };
+
+        // "cab" and "cats" score as much as "cat" but need a wildcard
+        assert_eq!(
+            word_trie.best_per_starting_letter("act*")[&'c'],
+            ("cat".to_string(), 4)
+        );
+        assert_eq!(word_trie.get_words("act*")[0], ("cat".to_string(), 4));
+    }
+
+    #[test]
+    pub fn test_tie_break_ordering() {
+        let mut word_trie = WordTrie::default();
+        let words = ["at", "cab", "cat", "tac", "act", "cats", "tax"];
+        words.iter().for_each(|word| word_trie.insert(word));
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('c', 3), ('a', 1), ('t', 0), ('b', 3), ('x', 10)]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            word_trie.get_words("act*"),
+            [
+                // no wildcards, then alphabetical
+                ("act".to_string(), 4u32),
+                ("cat".to_string(), 4u32),
+                ("tac".to_string(), 4u32),
+                // one wildcard, longest first
+                ("cats".to_string(), 4u32),
+                ("cab".to_string(), 4u32),
+                // lower score despite using no wildcards
+                ("at".to_string(), 1u32),
+                ("tax".to_string(), 1u32),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_score_only_ordering() {
+        let mut word_trie = WordTrie::default();
+        let words = ["at", "cab", "cat", "tac", "act", "cats", "tax"];
+        words.iter().for_each(|word| word_trie.insert(word));
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('c', 3), ('a', 1), ('t', 0), ('b', 3), ('x', 10)]),
+            ordering: ResultOrdering::ScoreOnly,
+            ..Default::default()
+        };
+
+        let scores = word_trie
+            .get_words("act*")
+            .into_iter()
+            .map(|(_word, score)| score)
+            .collect::<Vec<_>>();
+
+        assert_eq!(scores, [4, 4, 4, 4, 4, 1, 1]);
+    }
+
+    #[test]
+    pub fn test_preserve_case() {
+        let mut word_trie = WordTrie::default().with_preserve_case(true);
+        word_trie.insert_all(["Aachen", "ache"]);
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('a', 1), ('c', 3), ('h', 4), ('e', 1), ('n', 1)]),
+            ..Default::default()
+        };
+
+        assert_eq!(word_trie.get_words("aachen")[0], ("Aachen".to_string(), 11));
+        assert_eq!(
+            word_trie.best_word("aachen"),
+            Some(("Aachen".to_string(), 11))
+        );
+        assert_eq!(
+            word_trie.best_per_starting_letter("aachen")[&'a'],
+            ("Aachen".to_string(), 11)
+        );
+        assert_eq!(word_trie.get_words_with_tiles("aachen")[0].0, "Aachen");
+    }
+
+    #[test]
+    pub fn test_get_words_with_tiles() {
+        let mut word_trie = WordTrie::default();
+        ["cab", "don't"].iter().for_each(|w| word_trie.insert(w));
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('c', 3), ('a', 1), ('b', 3)]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            word_trie.get_words_with_tiles("ca*"),
+            [(
+                "cab".to_string(),
+                4u32,
+                vec![Tile::Letter('c'), Tile::Letter('a'), Tile::Wildcard('b')]
+            )]
+        );
+        assert_eq!(
+            word_trie.get_words_with_tiles("dont"),
+            [(
+                "don't".to_string(),
+                0u32,
+                vec![
+                    Tile::Letter('d'),
+                    Tile::Letter('o'),
+                    Tile::Letter('n'),
+                    Tile::Letter('t')
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    pub fn test_get_words_scored_sorted() {
+        let mut word_trie = WordTrie::default();
+        let words = ["at", "cab", "cat", "tac", "act", "cats", "tax"];
+        words.iter().for_each(|word| word_trie.insert(word));
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('c', 3), ('a', 1), ('t', 0), ('b', 3), ('x', 10)]),
+            ordering: ResultOrdering::ScoreOnly,
+            ..Default::default()
+        };
+
+        let expected = [
+            ("act".to_string(), 4u32),
+            ("cab".to_string(), 4u32),
+            ("cat".to_string(), 4u32),
+            ("cats".to_string(), 4u32),
+            ("tac".to_string(), 4u32),
+            ("at".to_string(), 1u32),
+            ("tax".to_string(), 1u32),
+        ];
+        for _ in 0..10 {
+            assert_eq!(word_trie.get_words_scored_sorted("act*"), expected);
+        }
+    }
+
+    #[test]
+    pub fn test_get_words_limited() {
+        // Every word of up to four letters made out of "abcde"
+        let mut word_trie = WordTrie::default();
+        let mut words = vec![String::new()];
+        for _ in 0..4 {
+            words = words
+                .iter()
+                .flat_map(|word| "abcde".chars().map(move |ch| format!("{word}{ch}")))
+                .collect();
+            word_trie.insert_all(&words);
+        }
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from(SCRABBLE_SCORES),
+            ..Default::default()
+        }
+        .with_bingo_bonus(50, 4);
+
+        for letters in ["abcde", "aabd*", "e**", "dcba"] {
+            let all_words = word_trie.get_words(letters);
+            for limit in [0, 1, 5, 20, all_words.len() + 1] {
+                let mut expected = all_words.clone();
+                expected.truncate(limit);
+
+                assert_eq!(
+                    word_trie.get_words_limited(letters, limit),
+                    expected,
+                    "{letters} {limit}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    pub fn test_alphabetical_ordering() {
+        let mut word_trie = WordTrie::default();
+        let words = ["at", "cab", "cat", "tax"];
+        words.iter().for_each(|word| word_trie.insert(word));
+        let mut word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('c', 3), ('a', 1), ('t', 1), ('b', 3), ('x', 10)]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            word_trie.get_words("abctx"),
+            [
+                ("tax".to_string(), 12u32),
+                ("cab".to_string(), 7u32),
+                ("cat".to_string(), 5u32),
+                ("at".to_string(), 2u32),
+            ]
+        );
+
+        word_trie.ordering = ResultOrdering::Alphabetical;
+
+        assert_eq!(
+            word_trie.get_words("abctx"),
+            [
+                ("at".to_string(), 2u32),
+                ("cab".to_string(), 7u32),
+                ("cat".to_string(), 5u32),
+                ("tax".to_string(), 12u32),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_get_words_scored_on_board() {
+        let mut word_trie = WordTrie::default();
+        let words = ["rad", "radar", "dart"];
+        words.iter().for_each(|word| word_trie.insert(word));
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('r', 1), ('t', 2), ('d', 3)]),
+            ..Default::default()
+        };
+        let multipliers = [
+            TileMultiplier::NONE,
+            TileMultiplier::NONE,
+            TileMultiplier::letter(3),
+            TileMultiplier::word(2),
+        ];
+
+        assert_eq!(
+            word_trie.get_words_scored_on_board("radart", &multipliers),
+            [
+                // (1 + 0 + 3 * 3 + 0 + 1) * 2
+                ("radar".to_string(), 22u32),
+                // (3 + 0 + 1 * 3 + 2) * 2
+                ("dart".to_string(), 16u32),
+                // 1 + 0 + 3 * 3
+                ("rad".to_string(), 10u32),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_wildcard_is_placed_where_it_scores_the_most() {
+        let mut word_trie = WordTrie::default();
+        word_trie.insert_all(["aab", "aba"]);
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('a', 1), ('b', 3)]),
+            ..Default::default()
+        };
+        let multipliers = [
+            TileMultiplier::NONE,
+            TileMultiplier::NONE,
+            TileMultiplier::letter(3),
+        ];
+
+        assert_eq!(
+            word_trie.get_words_scored_on_board("ab*", &multipliers),
+            [
+                // 0 + 1 + 3 * 3, with the wildcard as either "a"
+                ("aab".to_string(), 10u32),
+                // 0 + 3 + 1 * 3, with the wildcard as the first "a"
+                ("aba".to_string(), 6u32),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_board_score_does_not_overflow() {
+        let mut word_trie = WordTrie::default();
+        word_trie.insert("zzzzzzz");
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('z', 10)]),
+            ..Default::default()
+        };
+
+        let triple_everything = [TileMultiplier { letter: 3, word: 3 }; 7];
+        assert_eq!(
+            word_trie.get_words_scored_on_board("zzzzzzz", &triple_everything),
+            // 7 * 10 * 3 * 3^7
+            [("zzzzzzz".to_string(), 459_270u32)]
+        );
+
+        let huge = [TileMultiplier {
+            letter: u32::MAX,
+            word: u32::MAX,
+        }; 7];
+        assert_eq!(
+            word_trie.get_words_scored_on_board("zzzzzzz", &huge),
+            [("zzzzzzz".to_string(), u32::MAX)]
+        );
+    }
+
+    #[test]
+    pub fn test_bingo_bonus() {
+        let mut word_trie = WordTrie::default();
+        let words = ["zanier", "retains", "stain"];
+        words.iter().for_each(|word| word_trie.insert(word));
+        let score_map = "abcdefghijklmnopqrstuvwxy"
+            .chars()
+            .map(|ch| (ch, 1))
+            .chain([('z', 10)])
+            .collect();
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map,
+            ..Default::default()
+        }
+        .with_bingo_bonus(50, 7);
+
+        assert_eq!(
+            word_trie.get_words("zaretins"),
+            [
+                ("retains".to_string(), 57u32),
+                ("zanier".to_string(), 15u32),
+                ("stain".to_string(), 5u32),
+            ]
+        );
+    }
+
+    #[test]
+    pub fn test_bingo_bonus_skips_punctuation() {
+        let mut word_trie = WordTrie::default();
+        let words = ["don't", "dont", "do"];
+        words.iter().for_each(|word| word_trie.insert(word));
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('d', 1), ('o', 1), ('n', 1), ('t', 1)]),
+            ..Default::default()
+        }
+        .with_bingo_bonus(50, 4);
+
+        assert_eq!(
+            word_trie.get_words("dont"),
+            [
+                ("don't".to_string(), 54u32),
+                ("dont".to_string(), 54u32),
+                ("do".to_string(), 2u32),
+            ]
+        );
+        assert_eq!(word_trie.best_word("dont"), Some(("don't".to_string(), 54)));
+        assert_eq!(
+            word_trie.get_words_limited("dont", 2),
+            word_trie.get_words("dont")[..2]
+        );
+    }
+
+    #[test]
+    pub fn test_score_does_not_wrap_past_u8() {
+        let mut word_trie = WordTrie::default();
+        word_trie.insert("quizzes");
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([
+                ('q', 90),
+                ('u', 1),
+                ('i', 1),
+                ('z', 80),
+                ('e', 1),
+                ('s', 1),
+            ]),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            word_trie.get_words("quizzes"),
+            [("quizzes".to_string(), 254u32)]
+        );
+
+        let word_trie = ScoredWordTrie {
+            score_map: HashMap::from([
+                ('q', 90),
+                ('u', 1),
+                ('i', 1),
+                ('z', 90),
+                ('e', 1),
+                ('s', 1),
+            ]),
+            ..word_trie
+        };
+
+        assert_eq!(
+            word_trie.get_words("quizzes"),
+            [("quizzes".to_string(), 274u32)]
+        );
+    }
+
+    #[test]
+    pub fn test_wildcards_score_zero() {
+        let mut word_trie = WordTrie::default();
+        word_trie.insert("cab");
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('c', 3), ('a', 1), ('b', 3)]),
+            ..Default::default()
+        };
+
+        assert_eq!(word_trie.get_words("cab"), [("cab".to_string(), 7u32)]);
+        assert_eq!(word_trie.get_words("ca*"), [("cab".to_string(), 4u32)]);
+        assert_eq!(word_trie.get_words("c**"), [("cab".to_string(), 3u32)]);
+    }
+
+    #[test]
+    pub fn test_cancelled_search_returns_none() {
+        let mut word_trie = WordTrie::default();
+        ["cat", "act", "tac", "cab"]
+            .iter()
+            .for_each(|w| word_trie.insert(w));
+        let word_trie = ScoredWordTrie {
+            word_trie,
+            score_map: HashMap::from([('c', 3), ('a', 1), ('t', 1), ('b', 3)]),
+            ..Default::default()
+        };
+        let re = Regex::new("^c").expect("valid regex");
+
+        let cancelled = AtomicBool::new(false);
+        assert_eq!(
+            word_trie.get_words_cancellable("cat", &cancelled),
+            Some(word_trie.get_words("cat"))
+        );
+        assert_eq!(
+            word_trie.get_word_matches_cancellable("cab", &re, &cancelled),
+            Some(vec![("cab".to_string(), 7)])
+        );
+
+        cancelled.store(true, Ordering::Relaxed);
+        assert_eq!(word_trie.get_words_cancellable("***", &cancelled), None);
+        assert_eq!(
+            word_trie.get_word_matches_cancellable("***", &re, &cancelled),
+            None
         );
     }
 }
@@ -0,0 +1,154 @@
+use super::{ResultOrdering, ScoredWordTrie};
+use crate::file_reader::FileReader;
+use crate::word_trie::{Alphabet, WordTrie};
+use crate::BuildError;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Builds a [`ScoredWordTrie`] from its words and scores files.
+///
+/// # Usage
+///
+/// ```ignore
+/// let trie = ScoredWordTrieBuilder::default()
+///     .words_from_file("./words.txt")
+///     .scores_from_file("./char_scores.txt")
+///     .bingo_bonus(50)
+///     .build()?;
+/// ```
+pub struct ScoredWordTrieBuilder {
+    words_paths: Vec<PathBuf>,
+    scores_path: Option<PathBuf>,
+    alphabet: Alphabet,
+    default_score: i32,
+    ordering: ResultOrdering,
+    bingo_bonus: u32,
+    rack_size: usize,
+}
+
+impl Default for ScoredWordTrieBuilder {
+    fn default() -> Self {
+        Self {
+            words_paths: Vec::new(),
+            scores_path: None,
+            alphabet: Alphabet::default(),
+            default_score: 0,
+            ordering: ResultOrdering::default(),
+            bingo_bonus: 0,
+            // The number of tiles on a Scrabble rack
+            rack_size: 7,
+        }
+    }
+}
+
+impl ScoredWordTrieBuilder {
+    /// Adds the words of a words file. Can be called more than once to merge several
+    /// files, such as a base dictionary and a custom word list.
+    pub fn words_from_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.words_paths.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Reads the scores of the letters from a scores file. Without one, every letter
+    /// is worth the [`default_score`].
+    ///
+    /// [`default_score`]: ScoredWordTrieBuilder::default_score
+    pub fn scores_from_file(mut self, path: impl AsRef<Path>) -> Self {
+        self.scores_path = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets the letters the words and scores can contain.
+    pub fn alphabet(mut self, alphabet: Alphabet) -> Self {
+        self.alphabet = alphabet;
+        self
+    }
+
+    /// Sets the score of the letters missing from the scores file.
+    pub fn default_score(mut self, score: i32) -> Self {
+        self.default_score = score;
+        self
+    }
+
+    /// Sets how the scored results are ordered.
+    pub fn ordering(mut self, ordering: ResultOrdering) -> Self {
+        self.ordering = ordering;
+        self
+    }
+
+    /// Adds the `bonus` to the score of words that use up the whole rack.
+    pub fn bingo_bonus(mut self, bonus: u32) -> Self {
+        self.bingo_bonus = bonus;
+        self
+    }
+
+    /// Sets the number of letters a word needs to get the bingo bonus, 7 by default.
+    pub fn rack_size(mut self, rack_size: usize) -> Self {
+        self.rack_size = rack_size;
+        self
+    }
+
+    /// Reads the files and builds the [`ScoredWordTrie`].
+    pub fn build(self) -> Result<ScoredWordTrie, BuildError> {
+        if self.words_paths.is_empty() {
+            return Err(BuildError::MissingWords);
+        }
+
+        let mut word_trie = WordTrie::with_alphabet(self.alphabet);
+        word_trie.insert_from_files(self.words_paths.iter().map(PathBuf::as_path))?;
+
+        let score_map = match &self.scores_path {
+            Some(path) => FileReader::parse_scores_file(path, self.alphabet)
+                .map_err(|err| BuildError::ParseFile(err.into()))?,
+            None => HashMap::new(),
+        };
+
+        Ok(ScoredWordTrie {
+            word_trie,
+            score_map,
+            score_sets: BTreeMap::new(),
+            default_score: self.default_score,
+            ordering: self.ordering,
+            bingo_bonus: self.bingo_bonus,
+            rack_size: self.rack_size,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_build() {
+        let trie = ScoredWordTrieBuilder::default()
+            .words_from_file("./test_data/words.txt")
+            .words_from_file("./test_data/custom_words.txt")
+            .scores_from_file("./test_data/commented_scores.txt")
+            .default_score(1)
+            .bingo_bonus(50)
+            .rack_size(3)
+            .build()
+            .expect("should build the trie");
+
+        // a=1, c=3 from the scores file, t=1 by default, and the bingo bonus
+        assert_eq!(
+            trie.get_words("cat"),
+            [("cat".to_string(), 55), ("tac".to_string(), 55)]
+        );
+        assert_eq!(
+            trie.get_words("dart"),
+            [("rad".to_string(), 53), ("dart".to_string(), 4)]
+        );
+    }
+
+    #[test]
+    fn test_build_without_words() {
+        let result = ScoredWordTrieBuilder::default()
+            .scores_from_file("./test_data/commented_scores.txt")
+            .build();
+
+        assert!(matches!(result, Err(BuildError::MissingWords)));
+    }
+}
@@ -1,42 +1,336 @@
+mod best_first;
+mod child_map;
+mod dawg;
+mod letter_counts;
 mod node;
 mod path;
+mod search_session;
+mod words_iter;
 
+use crate::InvalidWordError;
+pub(crate) use best_first::BestFirstIter;
+use child_map::ChildMap;
+pub use dawg::Dawg;
+pub(crate) use letter_counts::LetterCounts;
 use node::*;
+pub(crate) use path::Path;
 use path::*;
 use regex::Regex;
-use std::collections::{HashMap, VecDeque};
+pub use search_session::SearchSession;
+use std::collections::HashMap;
+use std::sync::atomic::AtomicBool;
+use words_iter::*;
+pub use words_iter::{FoundWord, Tile};
 
-#[derive(Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct WordTrie {
     root: Node,
+    alphabet: Alphabet,
+    punctuation: Vec<char>,
+    preserve_case: bool,
+}
+
+/// The punctuation allowed in words by default.
+pub const DEFAULT_PUNCTUATION: [char; 2] = ['\'', '-'];
+
+impl Default for WordTrie {
+    fn default() -> Self {
+        Self {
+            root: Node::default(),
+            alphabet: Alphabet::default(),
+            punctuation: DEFAULT_PUNCTUATION.to_vec(),
+            preserve_case: false,
+        }
+    }
+}
+
+/// Builds a Trie with the default settings from the words, like
+/// [`WordTrie::insert_all`].
+impl<S: AsRef<str>> FromIterator<S> for WordTrie {
+    fn from_iter<I: IntoIterator<Item = S>>(words: I) -> Self {
+        let mut trie = Self::default();
+        trie.insert_all(words);
+        trie
+    }
+}
+
+impl<S: AsRef<str>> Extend<S> for WordTrie {
+    fn extend<I: IntoIterator<Item = S>>(&mut self, words: I) {
+        self.insert_all(words);
+    }
+}
+
+/// The characters that are accepted as letters in words and racks.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Alphabet {
+    /// Only the characters between a-z or A-Z.
+    #[default]
+    Ascii,
+    /// Any alphabetic character, including accented letters like 'é' or 'ñ'.
+    Unicode,
+}
+
+impl Alphabet {
+    /// Returns `true` if the [`char`] is a letter of this alphabet.
+    pub fn is_letter(self, ch: char) -> bool {
+        match self {
+            Alphabet::Ascii => ch.is_ascii_alphabetic(),
+            Alphabet::Unicode => ch.is_alphabetic(),
+        }
+    }
 }
 
 impl WordTrie {
+    /// Creates an empty Trie that accepts the letters of the given [`Alphabet`].
+    pub fn with_alphabet(alphabet: Alphabet) -> Self {
+        Self {
+            alphabet,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the punctuation allowed in words, like the apostrophe in "don't".
+    ///
+    /// Punctuation does not need to be in the letters used for searching so "dont"
+    /// finds "don't".
+    pub fn with_punctuation(mut self, punctuation: &[char]) -> Self {
+        self.punctuation = punctuation.to_vec();
+        self
+    }
+
+    /// Returns the words with the casing they were inserted with, like "Aachen",
+    /// instead of lowercase. Words are still matched case-insensitively.
+    ///
+    /// A word inserted both in lowercase and with capitals, like "polish" and "Polish",
+    /// is returned in lowercase.
+    pub fn with_preserve_case(mut self, preserve_case: bool) -> Self {
+        self.preserve_case = preserve_case;
+        self
+    }
+
+    /// Restores the casing the word was inserted with if the Trie preserves case.
+    pub(crate) fn display_word(&self, word: String) -> String {
+        if !self.preserve_case {
+            return word;
+        }
+
+        match self
+            .root
+            .find(&word)
+            .and_then(|node| node.original.as_deref())
+        {
+            Some(original) => original.to_string(),
+            None => word,
+        }
+    }
+
+    pub fn alphabet(&self) -> Alphabet {
+        self.alphabet
+    }
+
+    pub fn punctuation(&self) -> &[char] {
+        &self.punctuation
+    }
+
+    /// Returns `true` if the [`char`] can be part of a word in this Trie.
+    pub fn is_valid_char(&self, ch: char) -> bool {
+        self.alphabet.is_letter(ch) || self.punctuation.contains(&ch)
+    }
+
     /// Inserts a words into the Trie
+    ///
+    /// The word is assumed to only contain valid characters, see
+    /// [`WordTrie::try_insert`] for words that haven't been validated yet.
     pub fn insert(&mut self, word: &str) {
         self.root.append_word(word);
     }
 
-    /// Gets all the words that could be built using the given letters.
-    pub fn get_words(&self, letters: &str) -> Vec<String> {
-        let mut words = Vec::new();
+    /// Returns `true` if the word was inserted into the Trie.
+    pub fn contains(&self, word: &str) -> bool {
+        self.root
+            .find(&word.to_lowercase())
+            .is_some_and(|node| node.is_word)
+    }
 
-        let letters_map = to_frequency_map(letters);
+    /// Returns whether each of the words is in the Trie, in the same order as the
+    /// words.
+    ///
+    /// The words are looked up in sorted order so that a word sharing a prefix with the
+    /// previous one resumes from where their prefix ends instead of from the root.
+    pub fn contains_all(&self, words: &[&str]) -> Vec<bool> {
+        let words = words
+            .iter()
+            .map(|word| word.to_lowercase().chars().collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let mut order = (0..words.len()).collect::<Vec<_>>();
+        order.sort_by(|a, b| words[*a].cmp(&words[*b]));
 
-        // Prepare the first search layer
-        let start_path = self.root.start_path(letters_map);
-        let mut search_stack = VecDeque::from([start_path]);
+        let mut found = vec![false; words.len()];
+        let mut prev_word: &[char] = &[];
+        // The nodes spelling each prefix of the previous word, as far as the Trie goes
+        let mut nodes = vec![&self.root];
 
-        // BFS
-        while let Some(path) = search_stack.pop_back() {
-            if path.node.is_word {
-                words.push(path.word_buf.clone());
+        for i in order {
+            let word = &words[i];
+            let shared = prev_word
+                .iter()
+                .zip(word)
+                .take_while(|(a, b)| a == b)
+                .count()
+                .min(nodes.len() - 1);
+            nodes.truncate(shared + 1);
+
+            for ch in &word[shared..] {
+                let Some(child) = nodes[nodes.len() - 1].children.get(ch) else {
+                    break;
+                };
+                nodes.push(child);
             }
 
-            step_trie(&path, &mut search_stack);
+            found[i] = nodes.len() == word.len() + 1 && nodes[word.len()].is_word;
+            prev_word = word;
+        }
+
+        found
+    }
+
+    /// Returns the number of times the word was inserted into the Trie, or `0` if it
+    /// isn't in the Trie.
+    pub fn count_of(&self, word: &str) -> u32 {
+        self.root
+            .find(&word.to_lowercase())
+            .map_or(0, |node| node.count)
+    }
+
+    /// Counts the nodes in the Trie, including the root.
+    pub fn node_count(&self) -> usize {
+        fn count(node: &Node) -> usize {
+            1 + node.children.values().map(count).sum::<usize>()
+        }
+
+        count(&self.root)
+    }
+
+    /// Returns the approximate number of bytes the Trie takes up in memory, counting
+    /// every node and the capacity of its children.
+    ///
+    /// Comparing it with [`Dawg::approx_memory_bytes`] tells whether compressing the
+    /// Trie is worth it.
+    pub fn approx_memory_bytes(&self) -> usize {
+        size_of::<Self>()
+            + self.punctuation.capacity() * size_of::<char>()
+            + self.root.allocated_bytes()
+    }
+
+    /// Inserts a word into the Trie if it only contains the letters of the Trie's
+    /// [`Alphabet`] and its allowed punctuation, the same as when reading words files.
+    pub fn try_insert(&mut self, word: &str) -> Result<(), InvalidWordError> {
+        if word.is_empty() || word.chars().any(|ch| !self.is_valid_char(ch)) {
+            return Err(InvalidWordError(word.to_string()));
         }
 
+        self.insert(word);
+        Ok(())
+    }
+
+    /// Inserts every word of an in-memory word list, such as a `Vec<String>` or a slice
+    /// of `&str`, into the Trie.
+    pub fn insert_all<I, S>(&mut self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
         words
+            .into_iter()
+            .for_each(|word| self.insert(word.as_ref()));
+    }
+
+    /// Gets all the words that could be built using the given letters.
+    #[cfg(not(feature = "parallel"))]
+    pub fn get_words(&self, letters: &str) -> Vec<String> {
+        self.iter_words(letters).collect()
+    }
+
+    /// Gets all the words that could be built using the given letters.
+    #[cfg(feature = "parallel")]
+    pub fn get_words(&self, letters: &str) -> Vec<String> {
+        self.get_words_parallel(letters)
+    }
+
+    /// Gets all the words that could be built using the given letters by searching
+    /// the branches of the first search layer in parallel.
+    #[cfg(feature = "parallel")]
+    pub fn get_words_parallel(&self, letters: &str) -> Vec<String> {
+        use rayon::prelude::*;
+
+        let (found, branches) = self.iter_found_words(letters).split();
+        let words = branches
+            .into_par_iter()
+            .flat_map_iter(|branch| branch)
+            .collect::<Vec<_>>();
+
+        found
+            .into_iter()
+            .chain(words)
+            .map(|found| self.display_word(found.word))
+            .collect()
+    }
+
+    /// Gets all the words that could be built using the given letters plus up to
+    /// `blanks` wildcards.
+    ///
+    /// Unlike [`WordTrie::get_words`], a `*` in `letters` is ignored so that the number
+    /// of wildcards only comes from `blanks`.
+    pub fn get_words_with_blanks(&self, letters: &str, blanks: usize) -> Vec<String> {
+        let mut letters_map = LetterCounts::new(letters, self.alphabet);
+        letters_map.set('*', blanks);
+
+        WordsIter::new(self.root.start_path(letters_map), &self.punctuation)
+            .map(|found| self.display_word(found.word))
+            .collect()
+    }
+
+    /// Lazily iterates over the words that could be built using the given letters.
+    ///
+    /// The search only advances as the iterator is consumed.
+    pub fn iter_words(&self, letters: &str) -> impl Iterator<Item = String> + '_ {
+        self.iter_found_words(letters)
+            .map(|found| self.display_word(found.word))
+    }
+
+    /// Lazily iterates over every word in the Trie, in alphabetical order.
+    pub fn iter(&self) -> impl Iterator<Item = String> + '_ {
+        self.iter_all_found_words()
+            .map(|found| self.display_word(found.word))
+    }
+
+    /// Lazily iterates over every word in the Trie as it is stored, in lowercase.
+    fn iter_all_found_words(&self) -> WordsIter<'_> {
+        let mut start_path = self.root.start_path(LetterCounts::default());
+        start_path.unlimited = true;
+        WordsIter::new(start_path, &self.punctuation)
+    }
+
+    /// Lazily iterates over the words that could be built using the given letters
+    /// together with the positions that were filled using a wildcard `*`.
+    pub(crate) fn iter_found_words(&self, letters: &str) -> WordsIter<'_> {
+        let letters_map = LetterCounts::new(letters, self.alphabet);
+        WordsIter::new(self.root.start_path(letters_map), &self.punctuation)
+    }
+
+    /// Searches the words that could be built using the given letters, stepping through
+    /// the path with the highest `bound` first.
+    pub(crate) fn iter_found_words_best_first<'a, F>(
+        &'a self,
+        letters: &str,
+        bound: F,
+    ) -> BestFirstIter<'a, F>
+    where
+        F: Fn(&Path<'a>) -> u32,
+    {
+        let letters_map = LetterCounts::new(letters, self.alphabet);
+        BestFirstIter::new(self.root.start_path(letters_map), &self.punctuation, bound)
     }
 
     pub fn get_words_sorted(&self, letters: &str) -> Vec<String> {
@@ -45,28 +339,192 @@ impl WordTrie {
         words
     }
 
-    /// Gets all the words that matches the given regular expression.
-    pub fn get_word_matches(&self, letters: &str, expr: &str) -> Result<Vec<String>, regex::Error> {
+    /// Gets up to `limit` words that could be built using the given letters, skipping
+    /// the first `offset` words.
+    ///
+    /// The words are sorted alphabetically so successive pages never overlap. Every
+    /// page searches for all the words again and the total number of words isn't
+    /// returned, so get it from [`WordTrie::get_words`] if it's needed.
+    pub fn get_words_page(&self, letters: &str, offset: usize, limit: usize) -> Vec<String> {
+        self.get_words_sorted(letters)
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .collect()
+    }
+
+    /// Gets all the words that start with the given prefix, including the prefix itself
+    /// if it is a word.
+    pub fn words_with_prefix(&self, prefix: &str) -> Vec<String> {
+        let mut prefix = prefix.to_lowercase();
         let mut words = Vec::new();
 
-        let re = Regex::new(expr)?;
+        if let Some(node) = self.root.find(&prefix) {
+            node.collect_words(&mut prefix, &mut words);
+        }
 
-        let letters_map = to_frequency_map(letters);
+        words
+            .into_iter()
+            .map(|word| self.display_word(word))
+            .collect()
+    }
 
-        // Prepare the first search layer
-        let start_path = self.root.start_path(letters_map);
-        let mut search_stack = VecDeque::from([start_path]);
+    /// Groups every word of the Trie with its anagrams, keyed by their letters sorted
+    /// alphabetically, e.g. "dart" and "drat" are both under "adrt".
+    ///
+    /// Punctuation is left out of the key since it doesn't use up any letters. Words
+    /// without any anagrams are left out if `skip_singletons` is `true`.
+    pub fn anagram_groups(&self, skip_singletons: bool) -> HashMap<String, Vec<String>> {
+        let mut groups: HashMap<String, Vec<String>> = HashMap::new();
+        for word in self.iter_all_found_words().map(|found| found.word) {
+            let mut letters = word
+                .chars()
+                .filter(|ch| !self.punctuation.contains(ch))
+                .collect::<Vec<_>>();
+            letters.sort_unstable();
 
-        // BFS
-        while let Some(path) = search_stack.pop_back() {
-            if path.node.is_word && re.is_match(&path.word_buf) {
-                words.push(path.word_buf.clone());
-            }
+            groups
+                .entry(letters.into_iter().collect())
+                .or_default()
+                .push(self.display_word(word));
+        }
 
-            step_trie(&path, &mut search_stack);
+        if skip_singletons {
+            groups.retain(|_, words| words.len() > 1);
         }
 
-        Ok(words)
+        groups
+    }
+
+    /// Gets all the words containing the letters of `subseq` in order, though not
+    /// necessarily next to each other, e.g. "abc" matches "alphabetic".
+    pub fn words_containing_subsequence(&self, subseq: &str) -> Vec<String> {
+        let subseq = subseq.to_lowercase().chars().collect::<Vec<_>>();
+        let mut words = Vec::new();
+
+        self.root
+            .collect_with_subsequence(&subseq, 0, &mut String::new(), &mut words);
+
+        words
+            .into_iter()
+            .map(|word| self.display_word(word))
+            .collect()
+    }
+
+    /// Gets the words that are at most `max_distance` insertions, deletions or
+    /// substitutions away from the `target`, together with their distance.
+    ///
+    /// The words are sorted by distance, closest first, then alphabetically.
+    pub fn fuzzy_search(&self, target: &str, max_distance: usize) -> Vec<(String, usize)> {
+        let target = target.to_lowercase().chars().collect::<Vec<_>>();
+        // The distances between the empty word and each prefix of the target
+        let first_row = (0..=target.len()).collect::<Vec<_>>();
+        let mut words = Vec::new();
+
+        for (ch, child) in self.root.children.iter() {
+            child.collect_fuzzy(
+                *ch,
+                &target,
+                &first_row,
+                max_distance,
+                &mut String::new(),
+                &mut words,
+            );
+        }
+
+        words.sort_by(|(a, a_distance), (b, b_distance)| {
+            a_distance.cmp(b_distance).then_with(|| a.cmp(b))
+        });
+        words
+            .into_iter()
+            .map(|(word, distance)| (self.display_word(word), distance))
+            .collect()
+    }
+
+    /// Gets all the words that end with the given suffix, including the suffix itself if
+    /// it is a word.
+    ///
+    /// The Trie is only indexed by prefix so this goes through every word in the Trie.
+    /// This is slower than [`WordTrie::words_with_prefix`] but avoids maintaining a
+    /// second, reversed Trie that would double the memory used.
+    pub fn words_with_suffix(&self, suffix: &str) -> Vec<String> {
+        let suffix = suffix.to_lowercase();
+
+        self.iter_all_found_words()
+            .map(|found| found.word)
+            .filter(|word| word.ends_with(&suffix))
+            .map(|word| self.display_word(word))
+            .collect()
+    }
+
+    /// Gets all the words that matches the given regular expression.
+    ///
+    /// If `letters` is empty, the whole dictionary is searched for matches instead.
+    pub fn get_word_matches(&self, letters: &str, expr: &str) -> Result<Vec<String>, regex::Error> {
+        let re = Regex::new(expr)?;
+        Ok(self.get_word_matches_compiled(letters, &re))
+    }
+
+    /// Gets all the words that matches the given regular expression as a whole.
+    ///
+    /// Unlike [`WordTrie::get_word_matches`], `art` only matches "art" and not "dart"
+    /// or "cart". Anchors already present in the expression are kept as is.
+    pub fn get_word_matches_full(
+        &self,
+        letters: &str,
+        expr: &str,
+    ) -> Result<Vec<String>, regex::Error> {
+        let re = Regex::new(&format!("^(?:{expr})$"))?;
+        Ok(self.get_word_matches_compiled(letters, &re))
+    }
+
+    /// Gets all the words that matches the given compiled regular expression.
+    ///
+    /// Use this instead of [`WordTrie::get_word_matches`] to avoid compiling the same
+    /// regular expression on every search.
+    pub fn get_word_matches_compiled(&self, letters: &str, re: &Regex) -> Vec<String> {
+        let mut words = Vec::new();
+        self.iter_match_candidates(letters, re).search(|found| {
+            if re.is_match(&found.word) {
+                words.push(self.display_word(found.word));
+            }
+        });
+        words
+    }
+
+    /// Lazily iterates over the words that could be built using the given letters and
+    /// could match the compiled regular expression, which is left to check on each word.
+    ///
+    /// If every match of the expression must start with the same literal prefix, only
+    /// the branch of the Trie spelling that prefix is searched.
+    ///
+    /// If there are no letters, every word of the Trie is checked against the expression
+    /// as if each letter was available any number of times.
+    pub(crate) fn iter_match_candidates(&self, letters: &str, re: &Regex) -> WordsIter<'_> {
+        let letters_map = LetterCounts::new(letters, self.alphabet);
+        let mut start_path = self.root.start_path(letters_map);
+        start_path.unlimited = letters.is_empty();
+        let prefix = literal_prefix(re.as_str()).unwrap_or_default();
+
+        WordsIter::with_prefix(start_path, &self.punctuation, &prefix)
+    }
+
+    /// Counts the words that could be built using the given letters and matches the
+    /// compiled regular expression, without collecting them.
+    ///
+    /// Returns `None` if the search was cancelled by setting the `cancelled` flag to
+    /// `true`.
+    pub fn count_word_matches(
+        &self,
+        letters: &str,
+        re: &Regex,
+        cancelled: &AtomicBool,
+    ) -> Option<usize> {
+        let mut count = 0;
+        self.iter_match_candidates(letters, re)
+            .cancellable(cancelled)
+            .search(|found| count += usize::from(re.is_match(&found.word)));
+        (!cancelled.load(std::sync::atomic::Ordering::Relaxed)).then_some(count)
     }
 
     pub fn get_word_matches_sorted(
@@ -80,19 +538,28 @@ impl WordTrie {
     }
 }
 
-/// Creates a frequency map of the available letters
-fn to_frequency_map(letters: &str) -> HashMap<char, usize> {
-    let letters = letters.chars().fold(HashMap::new(), |mut acc, ch| {
-        let Some(ch) = ch.to_lowercase().next() else {
-            return acc;
-        };
-        if ch.is_ascii_alphabetic() || ch == '*' {
-            *acc.entry(ch).or_insert(0) += 1;
-        }
-        acc
-    });
+/// Extracts the literal prefix that every match of the expression must start with.
+///
+/// Only expressions anchored to the start of the word with `^` have such a prefix.
+fn literal_prefix(expr: &str) -> Option<String> {
+    use regex_syntax::hir::literal::Extractor;
+    use regex_syntax::hir::Look;
+
+    let hir = regex_syntax::parse(expr).ok()?;
+    if !hir.properties().look_set_prefix().contains(Look::Start) {
+        return None;
+    }
 
-    letters
+    let literals = Extractor::new().extract(&hir);
+    let prefix = literals.longest_common_prefix()?;
+
+    // The prefix could end in the middle of a multi-byte character
+    let prefix = match std::str::from_utf8(prefix) {
+        Ok(prefix) => prefix,
+        Err(err) => std::str::from_utf8(&prefix[..err.valid_up_to()]).ok()?,
+    };
+
+    Some(prefix.to_string())
 }
 
 #[cfg(test)]
@@ -128,6 +595,611 @@ mod test {
         assert_eq!(trie.get_words_sorted("radart"), ["dart", "rad", "radar"]);
     }
 
+    #[test]
+    pub fn test_insert_all() {
+        let mut trie = WordTrie::default();
+        trie.insert_all(["rad", "radar", "dart"]);
+        trie.insert_all(vec!["radical".to_string(), "rad".to_string()]);
+
+        assert_eq!(trie.get_words_sorted("radart"), ["dart", "rad", "radar"]);
+        assert_eq!(trie.get_words_sorted("radicl"), ["rad"]);
+        assert_eq!(trie.get_words_sorted("radical"), ["rad", "radical"]);
+    }
+
+    #[test]
+    pub fn test_iter() {
+        let words = [
+            "radar",
+            "rad",
+            "t-shirt",
+            "dart",
+            "tee",
+            "rock'n'roll",
+            "drat",
+        ];
+        let trie = words.iter().collect::<WordTrie>();
+
+        let mut expected = words.to_vec();
+        expected.sort();
+
+        assert_eq!(trie.iter().collect::<Vec<_>>(), expected);
+        assert_eq!(WordTrie::default().iter().next(), None);
+    }
+
+    #[test]
+    pub fn test_collect_and_extend() {
+        let mut trie = "rad radar dart"
+            .split_whitespace()
+            .filter(|word| word.len() > 3)
+            .collect::<WordTrie>();
+
+        assert_eq!(trie.get_words_sorted("radart"), ["dart", "radar"]);
+
+        trie.extend(["rad", "tar"]);
+        trie.extend(vec!["art".to_string()]);
+
+        assert_eq!(
+            trie.get_words_sorted("radart"),
+            ["art", "dart", "rad", "radar", "tar"]
+        );
+    }
+
+    #[test]
+    pub fn test_count_word_matches() {
+        let mut trie = WordTrie::default();
+        trie.insert_all(["cat", "car", "cart", "dart", "act"]);
+        let re = Regex::new("^ca").unwrap();
+
+        assert_eq!(
+            trie.count_word_matches("", &re, &AtomicBool::new(false)),
+            Some(3)
+        );
+        assert_eq!(
+            trie.count_word_matches("rac", &re, &AtomicBool::new(false)),
+            Some(1)
+        );
+        assert_eq!(
+            trie.count_word_matches("", &re, &AtomicBool::new(true)),
+            None
+        );
+    }
+
+    #[test]
+    pub fn test_get_words_page() {
+        let trie = WordTrie::new_from_file(std::path::Path::new("./test_data/words.txt"))
+            .expect("fixture loads");
+        let letters = "radicalt*";
+        let all_words = trie.get_words_sorted(letters);
+
+        for limit in [1, 2, 3, 100] {
+            let pages = (0..all_words.len() + limit)
+                .step_by(limit)
+                .flat_map(|offset| trie.get_words_page(letters, offset, limit))
+                .collect::<Vec<_>>();
+            assert_eq!(pages, all_words, "pages of {limit}");
+        }
+        assert_eq!(
+            trie.get_words_page(letters, all_words.len(), 10),
+            Vec::<String>::new()
+        );
+    }
+
+    #[test]
+    pub fn test_anagram_groups() {
+        let mut trie = WordTrie::default();
+        trie.insert_all(["dart", "drat", "trad", "cat", "act", "dog", "don't", "dont"]);
+
+        let groups = trie.anagram_groups(false);
+        assert_eq!(groups["adrt"], ["dart", "drat", "trad"]);
+        assert_eq!(groups["act"], ["act", "cat"]);
+        assert_eq!(groups["dnot"], ["don't", "dont"]);
+        assert_eq!(groups["dgo"], ["dog"]);
+
+        let groups = trie.anagram_groups(true);
+        assert_eq!(groups.len(), 3);
+        assert!(!groups.contains_key("dgo"));
+    }
+
+    #[test]
+    pub fn test_words_containing_subsequence() {
+        let mut trie = WordTrie::default();
+        trie.insert_all(["abc", "alphabetic", "cab", "crab", "rabbit", "ab"]);
+
+        let mut words = trie.words_containing_subsequence("ABC");
+        words.sort();
+        assert_eq!(words, ["abc", "alphabetic"]);
+
+        // a substring search for "rb" would find nothing
+        let mut words = trie.words_containing_subsequence("rb");
+        words.sort();
+        assert_eq!(words, ["crab", "rabbit"]);
+
+        let mut words = trie.words_containing_subsequence("");
+        words.sort();
+        assert_eq!(words.len(), 6);
+    }
+
+    #[test]
+    pub fn test_fuzzy_search() {
+        let mut trie = WordTrie::default();
+        trie.insert_all(["cat", "cart", "car", "act", "cast", "dog", "at"]);
+
+        assert_eq!(
+            trie.fuzzy_search("cat", 1),
+            [
+                ("cat".to_string(), 0),
+                ("at".to_string(), 1),
+                ("car".to_string(), 1),
+                ("cart".to_string(), 1),
+                ("cast".to_string(), 1),
+            ]
+        );
+        assert_eq!(
+            trie.fuzzy_search("Cat", 2)
+                .into_iter()
+                .filter(|(_, distance)| *distance == 2)
+                .collect::<Vec<_>>(),
+            [("act".to_string(), 2)]
+        );
+        assert_eq!(trie.fuzzy_search("xyz", 2), []);
+    }
+
+    #[test]
+    pub fn test_preserve_case() {
+        let mut trie = WordTrie::default();
+        trie.insert_all(["Aachen", "ache", "NASA"]);
+
+        assert_eq!(trie.get_words_sorted("hecaan"), ["aachen", "ache"]);
+
+        let trie = trie.with_preserve_case(true);
+        assert_eq!(trie.get_words_sorted("HECAAN"), ["Aachen", "ache"]);
+        assert_eq!(trie.get_words_sorted("asan"), ["NASA"]);
+        assert_eq!(trie.words_with_prefix("aa"), ["Aachen"]);
+        assert!(trie.contains("aachen"));
+
+        let mut trie = WordTrie::default().with_preserve_case(true);
+        trie.insert_all(["Polish", "polish", "nasa", "NASA", "Ache", "ACHE"]);
+        assert_eq!(trie.iter().collect::<Vec<_>>(), ["ACHE", "nasa", "polish"]);
+    }
+
+    #[test]
+    pub fn test_contains_all() {
+        let mut trie = WordTrie::default();
+        trie.insert_all(["rad", "radar", "radical", "dart", "car"]);
+
+        assert_eq!(
+            trie.contains_all(&["radical", "ra", "car", "Radar", "radars", "", "rad", "cart"]),
+            [true, false, true, true, false, false, true, false]
+        );
+        assert!(trie.contains("dart"));
+        assert!(!trie.contains("dar"));
+    }
+
+    #[test]
+    pub fn test_count_of() {
+        let mut trie = WordTrie::default();
+        trie.insert_all(["radar", "rad", "Radar"]);
+
+        assert_eq!(trie.count_of("radar"), 2);
+        assert_eq!(trie.count_of("RAD"), 1);
+        assert_eq!(trie.count_of("ra"), 0);
+        assert_eq!(trie.count_of("dart"), 0);
+    }
+
+    #[test]
+    pub fn test_try_insert() {
+        let mut trie = WordTrie::default();
+
+        assert!(trie.try_insert("Radar").is_ok());
+        assert!(trie.try_insert("don't").is_ok());
+        assert!(matches!(trie.try_insert("ab1"), Err(InvalidWordError(word)) if word == "ab1"));
+        assert!(trie.try_insert("ice cream").is_err());
+        assert!(trie.try_insert("").is_err());
+
+        assert_eq!(trie.get_words_sorted("radar"), ["radar"]);
+        assert_eq!(trie.get_words_sorted("ab1"), Vec::<String>::new());
+        assert_eq!(trie.words_with_prefix("a"), Vec::<String>::new());
+    }
+
+    #[test]
+    pub fn test_iter_words_is_lazy() {
+        let mut trie = WordTrie::default();
+        let words = ["rad", "radar", "radical", "radiation", "dart", "art", "tar"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        let mut iter = trie.iter_found_words("radart");
+        let first_three = iter
+            .by_ref()
+            .take(3)
+            .map(|found| found.word)
+            .collect::<Vec<_>>();
+
+        assert_eq!(first_three.len(), 3);
+        assert!(
+            !iter.is_exhausted(),
+            "the search should not be exhausted after taking 3 words"
+        );
+
+        let mut all_words = first_three;
+        all_words.extend(iter.map(|found| found.word));
+        all_words.sort();
+        assert_eq!(all_words, trie.get_words_sorted("radart"));
+    }
+
+    #[test]
+    pub fn test_words_iter_matches_step_trie() {
+        use std::collections::VecDeque;
+        use std::path::Path;
+
+        let trie = WordTrie::new_from_file(Path::new("../words.txt")).expect("a words file");
+
+        for letters in ["retains", "retain*", "rock'n*", "t-shirt", "qu**"] {
+            let letters_map = LetterCounts::new(letters, trie.alphabet);
+
+            // Searches by cloning the letters and word of every path, like before
+            let mut search_stack = VecDeque::from([trie.root.start_path(letters_map)]);
+            let mut expected = Vec::new();
+            while let Some(path) = search_stack.pop_back() {
+                step_trie(&path, &trie.punctuation, &mut search_stack);
+                if path.node.is_word() {
+                    expected.push((path.word_buf, path.wildcards));
+                }
+            }
+
+            let mut found = trie
+                .iter_found_words(letters)
+                .map(|found| (found.word, found.wildcards))
+                .collect::<Vec<_>>();
+
+            expected.sort();
+            found.sort();
+            assert_eq!(found, expected, "{letters}");
+        }
+    }
+
+    #[test]
+    pub fn test_letter_counts_match_a_map() {
+        use std::path::Path;
+
+        let trie = WordTrie::new_from_file(Path::new("../words.txt")).expect("a words file");
+
+        for letters in ["retains", "retain*", "rock'n*", "t-shirt", "qu**", "zzz*"] {
+            let counts = LetterCounts::new(letters, trie.alphabet);
+            assert!(matches!(counts, LetterCounts::Ascii(_)));
+            let map = LetterCounts::Unicode(counts.iter().collect());
+
+            let search = |counts| {
+                let mut words = WordsIter::new(trie.root.start_path(counts), &trie.punctuation)
+                    .map(|found| (found.word, found.wildcards))
+                    .collect::<Vec<_>>();
+                words.sort();
+                words
+            };
+
+            assert_eq!(search(counts), search(map), "{letters}");
+        }
+    }
+
+    #[test]
+    pub fn test_cancelled_iter_stops_searching() {
+        let mut trie = WordTrie::default();
+        ["rad", "radar", "dart"].iter().for_each(|w| trie.insert(w));
+        let cancelled = AtomicBool::new(false);
+
+        let mut iter = trie.iter_found_words("radart").cancellable(&cancelled);
+        assert!(iter.next().is_some());
+        let visited = iter.visited;
+
+        cancelled.store(true, std::sync::atomic::Ordering::Relaxed);
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.visited, visited);
+        assert!(iter.is_exhausted());
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    pub fn test_get_words_parallel_matches_sequential() {
+        use std::path::Path;
+
+        let trie = WordTrie::new_from_file(Path::new("../words.txt")).expect("a words file");
+
+        for letters in ["retains", "qu*zzes*", "**aeinrst"] {
+            let mut sequential = trie.iter_words(letters).collect::<Vec<_>>();
+            let mut parallel = trie.get_words_parallel(letters);
+
+            sequential.sort();
+            parallel.sort();
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn test_save_and_load_trie() {
+        let mut trie = WordTrie::default();
+        let words = [
+            "rad",
+            "radar",
+            "radical",
+            "radiation",
+            "dart",
+            "cab",
+            "cabs",
+        ];
+        words.iter().for_each(|word| trie.insert(word));
+
+        let path = std::env::temp_dir().join(format!(
+            "lexicon_test_save_and_load_trie_{}.bin",
+            std::process::id()
+        ));
+        trie.save_to_file(&path).expect("save the trie");
+        let loaded = WordTrie::load_from_file(&path).expect("load the trie");
+        std::fs::remove_file(&path).expect("remove the saved trie");
+
+        assert!(
+            loaded.root == trie.root,
+            "the loaded nodes should be identical"
+        );
+        for letters in ["radart", "ca**", "radicaltion"] {
+            assert_eq!(
+                loaded.get_words_sorted(letters),
+                trie.get_words_sorted(letters)
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_words_with_prefix() {
+        let mut trie = WordTrie::default();
+        let words = ["rad", "radar", "radical", "radiation", "dart", "ra"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        let mut words = trie.words_with_prefix("rad");
+        words.sort();
+        assert_eq!(words, ["rad", "radar", "radiation", "radical"]);
+
+        let mut words = trie.words_with_prefix("RADI");
+        words.sort();
+        assert_eq!(words, ["radiation", "radical"]);
+
+        assert!(trie.words_with_prefix("rat").is_empty());
+    }
+
+    #[test]
+    pub fn test_words_with_suffix() {
+        let mut trie = WordTrie::default();
+        let words = ["rad", "radar", "radical", "dart", "car", "cart", "ar"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        let mut words = trie.words_with_suffix("ar");
+        words.sort();
+        assert_eq!(words, ["ar", "car", "radar"]);
+
+        let mut words = trie.words_with_suffix("ART");
+        words.sort();
+        assert_eq!(words, ["cart", "dart"]);
+
+        assert!(trie.words_with_suffix("xyz").is_empty());
+    }
+
+    #[test]
+    pub fn test_get_word_matches_compiled() {
+        let mut trie = WordTrie::default();
+        let words = [
+            "carbon", "car", "dart", "cam", "cart", "fart", "crime", "com", "rad", "radar",
+        ];
+        words.iter().for_each(|word| trie.insert(word));
+
+        for (letters, expr) in [
+            ("carbont", "car.*"),
+            ("cartf", ".*art"),
+            ("crimea*", "c.{1}m"),
+        ] {
+            let re = Regex::new(expr).expect("a valid regex");
+
+            let mut expected = trie.get_word_matches(letters, expr).expect("a valid regex");
+            let mut words = trie.get_word_matches_compiled(letters, &re);
+            expected.sort();
+            words.sort();
+
+            assert_eq!(words, expected);
+        }
+    }
+
+    #[test]
+    pub fn test_get_word_matches_full() {
+        let mut trie = WordTrie::default();
+        let words = ["art", "cart", "dart", "artsy", "rad", "radar"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        let mut words = trie
+            .get_word_matches("artcdsy", "art")
+            .expect("a valid regex");
+        words.sort();
+        assert_eq!(words, ["art", "artsy", "cart", "dart"]);
+
+        let words = trie
+            .get_word_matches_full("artcdsy", "art")
+            .expect("a valid regex");
+        assert_eq!(words, ["art"]);
+
+        let mut words = trie
+            .get_word_matches_full("artcdsy", "c?art|d.*")
+            .expect("a valid regex");
+        words.sort();
+        assert_eq!(words, ["art", "cart", "dart"]);
+
+        let mut words = trie
+            .get_word_matches_full("radart", "^r.*$")
+            .expect("a valid regex");
+        words.sort();
+        assert_eq!(words, ["rad", "radar"]);
+    }
+
+    #[test]
+    pub fn test_literal_prefix() {
+        assert_eq!(literal_prefix("^car"), Some("car".to_string()));
+        assert_eq!(literal_prefix("^car.*s$"), Some("car".to_string()));
+        assert_eq!(literal_prefix("^ca(r|t)"), Some("ca".to_string()));
+        assert_eq!(literal_prefix("^(car|cat)"), Some("ca".to_string()));
+        assert_eq!(literal_prefix("car"), None);
+        assert_eq!(literal_prefix("^car|dar"), None);
+    }
+
+    #[test]
+    pub fn test_regex_search_is_pruned_by_prefix() {
+        let mut trie = WordTrie::default();
+        let words = [
+            "carbon", "car", "dart", "cam", "cart", "fart", "crime", "com", "rad", "radar",
+            "carts", "scar", "arc", "bract", "tabs",
+        ];
+        words.iter().for_each(|word| trie.insert(word));
+
+        for (letters, expr) in [
+            ("carbont", "^car"),
+            ("carts**", "^ca(r|t)s?$"),
+            ("sbract*", "^(arc|ar)"),
+            ("radart", "^r.*$"),
+        ] {
+            let re = Regex::new(expr).expect("a valid regex");
+
+            let mut naive = trie.iter_found_words(letters);
+            let mut expected = naive
+                .by_ref()
+                .filter(|found| re.is_match(&found.word))
+                .map(|found| found.word)
+                .collect::<Vec<_>>();
+
+            let prefix = literal_prefix(expr).expect("an anchored prefix");
+            let start_path = trie
+                .root
+                .start_path(LetterCounts::new(letters, trie.alphabet));
+            let mut pruned = WordsIter::with_prefix(start_path, &trie.punctuation, &prefix);
+            let mut words = pruned
+                .by_ref()
+                .filter(|found| re.is_match(&found.word))
+                .map(|found| found.word)
+                .collect::<Vec<_>>();
+
+            expected.sort();
+            words.sort();
+            assert_eq!(words, expected, "{expr}");
+            assert_eq!(
+                trie.get_word_matches_sorted(letters, expr).unwrap(),
+                expected
+            );
+            assert!(
+                pruned.visited < naive.visited,
+                "{expr}: expected less than {} visited paths, got {}",
+                naive.visited,
+                pruned.visited
+            );
+        }
+    }
+
+    #[test]
+    pub fn test_multi_char_lowercase() {
+        // 'İ' lowercases to 'i' followed by a combining dot above
+        assert_eq!("İ".to_lowercase().chars().count(), 2);
+
+        let mut trie = WordTrie::with_alphabet(Alphabet::Unicode);
+        let words = ["İz", "iz"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        assert_eq!(trie.get_words_sorted("zİ"), ["iz", "i\u{307}z"]);
+        assert_eq!(trie.get_words_sorted("zI"), ["iz"]);
+        assert_eq!(
+            LetterCounts::new("İİ", Alphabet::Unicode),
+            LetterCounts::Unicode(HashMap::from([('i', 2), ('\u{307}', 2)]))
+        );
+        assert_eq!(
+            LetterCounts::new("İ", Alphabet::Ascii),
+            LetterCounts::default()
+        );
+    }
+
+    #[test]
+    pub fn test_regex_without_letters_searches_all_words() {
+        let mut trie = WordTrie::default();
+        let words = ["rad", "radar", "radical", "dart", "cart", "don't", "car"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        for expr in ["^rad", "ar", "^.a.$", "t$", "'"] {
+            let re = Regex::new(expr).unwrap();
+            let mut expected = words
+                .iter()
+                .filter(|word| re.is_match(word))
+                .map(|word| word.to_string())
+                .collect::<Vec<_>>();
+            expected.sort();
+
+            assert_eq!(trie.get_word_matches_sorted("", expr).unwrap(), expected);
+        }
+
+        assert!(trie.get_words("").is_empty());
+    }
+
+    #[test]
+    pub fn test_get_words_with_blanks() {
+        let mut trie = WordTrie::default();
+        let words = ["cat", "act", "cats", "chat", "chats"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        let words_with_blanks = |blanks| {
+            let mut words = trie.get_words_with_blanks("cat", blanks);
+            words.sort();
+            words
+        };
+
+        assert_eq!(words_with_blanks(0), ["act", "cat"]);
+        assert_eq!(words_with_blanks(1), ["act", "cat", "cats", "chat"]);
+        assert_eq!(
+            words_with_blanks(2),
+            ["act", "cat", "cats", "chat", "chats"]
+        );
+        let mut ignored_wildcards = trie.get_words_with_blanks("cat**", 0);
+        ignored_wildcards.sort();
+        assert_eq!(
+            ignored_wildcards,
+            ["act", "cat"],
+            "wildcards in the letters should be ignored"
+        );
+    }
+
+    #[test]
+    pub fn test_short_racks() {
+        let mut trie = WordTrie::with_alphabet(Alphabet::Unicode);
+        let words = ["a", "é", "ab", "él"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        assert_eq!(trie.get_words_sorted("a"), ["a"]);
+        assert_eq!(trie.get_words_sorted("É"), ["é"]);
+        assert_eq!(trie.get_words_sorted("*"), ["a", "é"]);
+        assert_eq!(trie.get_words_sorted("b*"), ["a", "ab", "é"]);
+    }
+
+    #[test]
+    pub fn test_punctuation_is_free() {
+        let mut trie = WordTrie::default();
+        let words = ["dont", "don't", "mother-in-law", "rock'n'roll", "cat"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        assert_eq!(trie.get_words_sorted("dont"), ["don't", "dont"]);
+        assert_eq!(trie.get_words_sorted("don't"), ["don't", "dont"]);
+        assert_eq!(trie.get_words_sorted("motherinlaw"), ["mother-in-law"]);
+        assert_eq!(trie.get_words_sorted("rocknroll"), ["rock'n'roll"]);
+        assert_eq!(trie.get_words_sorted("c*t"), ["cat"]);
+    }
+
+    #[test]
+    pub fn test_without_punctuation() {
+        let mut trie = WordTrie::default().with_punctuation(&[]);
+        let words = ["dont", "don't"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        assert_eq!(trie.get_words_sorted("dont"), ["dont"]);
+    }
+
     #[test]
     pub fn test_handle_wildcards() {
         let mut trie = WordTrie::default();
@@ -143,6 +1215,41 @@ mod test {
         );
     }
 
+    #[test]
+    pub fn test_multiple_wildcards_find_each_word_once() {
+        let mut trie = WordTrie::default();
+        let words = [
+            "cab", "cabs", "cam", "cams", "scab", "abba", "a", "ca", "don't",
+        ];
+        words.iter().for_each(|word| trie.insert(word));
+
+        for letters in ["ca**", "****", "a***", "**ab", "dt**"] {
+            let mut found = trie.get_words(letters);
+            let count = found.len();
+            found.sort();
+            found.dedup();
+
+            assert_eq!(found.len(), count, "duplicate words for {letters:?}");
+        }
+    }
+
+    #[test]
+    pub fn test_wildcard_duplicates_held_letter() {
+        let mut trie = WordTrie::default();
+        let words = ["a", "aa", "aaa", "aaaa"];
+        words.iter().for_each(|word| trie.insert(word));
+
+        assert_eq!(trie.get_words_sorted("aa*"), ["a", "aa", "aaa"]);
+        assert_eq!(trie.get_words_sorted("*aa"), ["a", "aa", "aaa"]);
+        assert_eq!(trie.get_words_sorted("a**"), ["a", "aa", "aaa"]);
+
+        // the tiles are used before the wildcard
+        let found = trie
+            .iter_found_words("aa*")
+            .find(|found| found.word == "aaa");
+        assert_eq!(found.map(|found| found.wildcards), Some(vec![2]));
+    }
+
     #[test]
     pub fn test_get_words_filtered() {
         let mut trie = WordTrie::default();
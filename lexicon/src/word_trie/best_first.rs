@@ -0,0 +1,103 @@
+use super::{step_trie, FoundWord, Node, Path, TrieNode};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+/// Yields the words found while searching through the Trie, always stepping through the
+/// path with the highest bound first.
+///
+/// As long as the `bound` of a path is never lower than the bound of the paths stepped
+/// into from it, no path left to search can lead to a word above [`peek_bound`].
+///
+/// Unlike [`WordsIter`], the paths own their letters and `word_buf` instead of undoing
+/// their steps in a shared buffer, since the paths are taken from the heap in the order
+/// of their bounds rather than depth first.
+///
+/// [`WordsIter`]: super::WordsIter
+/// [`peek_bound`]: BestFirstIter::peek_bound
+pub struct BestFirstIter<'a, F, N = Node> {
+    search_heap: BinaryHeap<BoundedPath<'a, N>>,
+    /// The punctuation that can be stepped through without using any letters.
+    punctuation: &'a [char],
+    bound: F,
+}
+
+impl<'a, F, N> BestFirstIter<'a, F, N>
+where
+    F: Fn(&Path<'a, N>) -> u32,
+    N: TrieNode,
+{
+    pub fn new(start_path: Path<'a, N>, punctuation: &'a [char], bound: F) -> Self {
+        let search_heap = BinaryHeap::from([BoundedPath {
+            bound: bound(&start_path),
+            path: start_path,
+        }]);
+
+        Self {
+            search_heap,
+            punctuation,
+            bound,
+        }
+    }
+
+    /// Returns the highest bound of the paths left to search, or `None` once there are
+    /// none left.
+    pub fn peek_bound(&self) -> Option<u32> {
+        self.search_heap.peek().map(|path| path.bound)
+    }
+}
+
+impl<'a, F, N> Iterator for BestFirstIter<'a, F, N>
+where
+    F: Fn(&Path<'a, N>) -> u32,
+    N: TrieNode,
+{
+    type Item = FoundWord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut next_paths = VecDeque::new();
+
+        while let Some(BoundedPath { path, .. }) = self.search_heap.pop() {
+            step_trie(&path, self.punctuation, &mut next_paths);
+            self.search_heap
+                .extend(next_paths.drain(..).map(|path| BoundedPath {
+                    bound: (self.bound)(&path),
+                    path,
+                }));
+
+            if path.node.is_word() {
+                return Some(FoundWord {
+                    word: path.word_buf,
+                    wildcards: path.wildcards,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+/// A path ordered by its bound only.
+struct BoundedPath<'a, N> {
+    bound: u32,
+    path: Path<'a, N>,
+}
+
+impl<N> PartialEq for BoundedPath<'_, N> {
+    fn eq(&self, other: &Self) -> bool {
+        self.bound == other.bound
+    }
+}
+
+impl<N> Eq for BoundedPath<'_, N> {}
+
+impl<N> PartialOrd for BoundedPath<'_, N> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N> Ord for BoundedPath<'_, N> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bound.cmp(&other.bound)
+    }
+}
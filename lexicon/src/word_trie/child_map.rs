@@ -0,0 +1,168 @@
+use std::ops::Index;
+
+/// The children of a node, keyed by the [`char`] leading to them.
+///
+/// Nodes only have a few children so they are kept next to each other in a [`Vec`]
+/// sorted by their [`char`], which is looked up with a binary search. This avoids the
+/// hashing and scattered buckets of a `HashMap`, and iterates in alphabetical order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChildMap<T> {
+    children: Vec<(char, T)>,
+}
+
+impl<T> Default for ChildMap<T> {
+    fn default() -> Self {
+        Self {
+            children: Vec::new(),
+        }
+    }
+}
+
+impl<T> ChildMap<T> {
+    pub fn get(&self, ch: &char) -> Option<&T> {
+        let i = self.position(ch).ok()?;
+        Some(&self.children[i].1)
+    }
+
+    #[cfg(test)]
+    pub fn contains_key(&self, ch: &char) -> bool {
+        self.position(ch).is_ok()
+    }
+
+    /// Gets the child reached through the [`char`], inserting a default one if there
+    /// is none.
+    pub fn get_or_insert_default(&mut self, ch: char) -> &mut T
+    where
+        T: Default,
+    {
+        let i = self.position(&ch).unwrap_or_else(|i| {
+            self.children.insert(i, (ch, T::default()));
+            i
+        });
+        &mut self.children[i].1
+    }
+
+    /// Iterates over the children in the alphabetical order of their [`char`].
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = (&char, &T)> {
+        self.children.iter().map(|(ch, child)| (ch, child))
+    }
+
+    pub fn keys(&self) -> impl Iterator<Item = &char> {
+        self.children.iter().map(|(ch, _)| ch)
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.children.iter().map(|(_, child)| child)
+    }
+
+    /// Returns the number of bytes allocated to hold the children, including the unused
+    /// capacity.
+    pub fn allocated_bytes(&self) -> usize {
+        self.children.capacity() * size_of::<(char, T)>()
+    }
+
+    fn position(&self, ch: &char) -> Result<usize, usize> {
+        self.children.binary_search_by_key(ch, |(ch, _)| *ch)
+    }
+}
+
+impl<T> Index<&char> for ChildMap<T> {
+    type Output = T;
+
+    fn index(&self, ch: &char) -> &Self::Output {
+        self.get(ch).expect("no child for the char")
+    }
+}
+
+impl<T> FromIterator<(char, T)> for ChildMap<T> {
+    /// Collects the children, keeping the last one of any repeated [`char`] like a
+    /// `HashMap` does.
+    fn from_iter<I: IntoIterator<Item = (char, T)>>(iter: I) -> Self {
+        let mut children = iter.into_iter().collect::<Vec<_>>();
+        // The sort is stable so the last of the repeated chars stays last
+        children.sort_by_key(|(ch, _)| *ch);
+        children.reverse();
+        children.dedup_by_key(|(ch, _)| *ch);
+        children.reverse();
+
+        Self { children }
+    }
+}
+
+impl<T, const N: usize> From<[(char, T); N]> for ChildMap<T> {
+    fn from(children: [(char, T); N]) -> Self {
+        children.into_iter().collect()
+    }
+}
+
+impl<T> IntoIterator for ChildMap<T> {
+    type Item = (char, T);
+    type IntoIter = std::vec::IntoIter<(char, T)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.children.into_iter()
+    }
+}
+
+/// Serialized as a map so that the format is the same as when the children were kept
+/// in a `HashMap`.
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for ChildMap<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(self.iter())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: serde::Deserialize<'de>> serde::Deserialize<'de> for ChildMap<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let children = std::collections::BTreeMap::<char, T>::deserialize(deserializer)?;
+        Ok(children.into_iter().collect())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_insert_keeps_children_sorted() {
+        let mut children = ChildMap::default();
+        for ch in ['r', 'a', 'd', 'a', 'z'] {
+            *children.get_or_insert_default(ch) += 1;
+        }
+
+        assert_eq!(
+            children.iter().collect::<Vec<_>>(),
+            [(&'a', &2), (&'d', &1), (&'r', &1), (&'z', &1)]
+        );
+        assert_eq!(children.get(&'d'), Some(&1));
+        assert_eq!(children.get(&'b'), None);
+    }
+
+    #[test]
+    fn test_collect_keeps_the_last_repeated_child() {
+        let children = ChildMap::from([('b', 1), ('a', 2), ('b', 3)]);
+
+        assert_eq!(
+            children.into_iter().collect::<Vec<_>>(),
+            [('a', 2), ('b', 3)]
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_decode_children_saved_as_a_hash_map() {
+        use std::collections::HashMap;
+
+        let config = bincode::config::standard();
+        let saved = HashMap::from([('z', 1), ('a', 2), ('m', 3)]);
+        let bytes = bincode::serde::encode_to_vec(&saved, config).expect("encodable");
+
+        let (children, _): (ChildMap<i32>, _) =
+            bincode::serde::decode_from_slice(&bytes, config).expect("decodable");
+
+        assert_eq!(children, ChildMap::from([('a', 2), ('m', 3), ('z', 1)]));
+    }
+}
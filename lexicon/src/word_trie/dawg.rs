@@ -0,0 +1,203 @@
+use super::{Alphabet, ChildMap, LetterCounts, Node, TrieNode, WordTrie, WordsIter};
+use std::collections::{HashMap, HashSet};
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A directed acyclic word graph (DAWG) built from a [`WordTrie`].
+///
+/// Equivalent subtrees of the Trie are merged so that common suffixes like "-ing" or
+/// "-s" are only stored once. The DAWG is read-only but can be searched exactly like
+/// the [`WordTrie`] it was built from.
+///
+/// The casing the words were inserted with is dropped while compressing, so the words
+/// are always returned in lowercase, even if the Trie preserved case.
+pub struct Dawg {
+    root: Arc<DawgNode>,
+    alphabet: Alphabet,
+    punctuation: Vec<char>,
+}
+
+#[derive(Default, PartialEq)]
+pub struct DawgNode {
+    children: ChildMap<Arc<DawgNode>>,
+    is_word: bool,
+}
+
+/// Identifies equivalent nodes by their word flag and their already merged children.
+type Signature = (bool, Vec<(char, *const DawgNode)>);
+
+impl WordTrie {
+    /// Compresses the Trie into a [`Dawg`] by merging its equivalent subtrees.
+    ///
+    /// The casing of the words is dropped, see [`WordTrie::with_preserve_case`].
+    pub fn compress(self) -> Dawg {
+        let mut registry = HashMap::new();
+        let root = merge_node(self.root, &mut registry);
+        Dawg {
+            root,
+            alphabet: self.alphabet,
+            punctuation: self.punctuation,
+        }
+    }
+}
+
+impl Dawg {
+    /// Gets all the words that could be built using the given letters.
+    pub fn get_words(&self, letters: &str) -> Vec<String> {
+        let letters_map = LetterCounts::new(letters, self.alphabet);
+        WordsIter::new(self.root.start_path(letters_map), &self.punctuation)
+            .map(|found| found.word)
+            .collect()
+    }
+
+    pub fn get_words_sorted(&self, letters: &str) -> Vec<String> {
+        let mut words = self.get_words(letters);
+        words.sort();
+        words
+    }
+
+    /// Counts the distinct nodes in the DAWG, including the root.
+    pub fn node_count(&self) -> usize {
+        self.distinct_nodes().len()
+    }
+
+    /// Returns the approximate number of bytes the DAWG takes up in memory, counting
+    /// every distinct node once along with the capacity of its children.
+    pub fn approx_memory_bytes(&self) -> usize {
+        // Each node is allocated together with the two reference counts of its `Arc`
+        let node_bytes = |node: &DawgNode| {
+            2 * size_of::<usize>() + size_of::<DawgNode>() + node.children.allocated_bytes()
+        };
+
+        size_of::<Self>()
+            + self.punctuation.capacity() * size_of::<char>()
+            + self
+                .distinct_nodes()
+                .into_iter()
+                .map(node_bytes)
+                .sum::<usize>()
+    }
+
+    /// Returns every distinct node in the DAWG, including the root.
+    fn distinct_nodes(&self) -> Vec<&DawgNode> {
+        let mut visited = HashSet::new();
+        let mut nodes = Vec::new();
+        let mut stack = vec![&self.root];
+
+        while let Some(node) = stack.pop() {
+            if visited.insert(Arc::as_ptr(node)) {
+                nodes.push(node.as_ref());
+                stack.extend(node.children.values());
+            }
+        }
+
+        nodes
+    }
+}
+
+/// Converts the [`Node`] into a [`DawgNode`], reusing an equivalent node from the
+/// registry if one was already built.
+fn merge_node(node: Node, registry: &mut HashMap<Signature, Arc<DawgNode>>) -> Arc<DawgNode> {
+    let children = node
+        .children
+        .into_iter()
+        .map(|(ch, child)| (ch, merge_node(child, registry)))
+        .collect::<ChildMap<_>>();
+
+    let child_ptrs = children
+        .iter()
+        .map(|(ch, child)| (*ch, Arc::as_ptr(child)))
+        .collect::<Vec<_>>();
+
+    registry
+        .entry((node.is_word, child_ptrs))
+        .or_insert_with(|| {
+            Arc::new(DawgNode {
+                children,
+                is_word: node.is_word,
+            })
+        })
+        .clone()
+}
+
+impl TrieNode for DawgNode {
+    fn is_word(&self) -> bool {
+        self.is_word
+    }
+
+    fn child(&self, ch: &char) -> Option<&Self> {
+        self.children.get(ch).map(Arc::as_ref)
+    }
+
+    fn children(&self) -> impl DoubleEndedIterator<Item = (&char, &Self)> {
+        self.children.iter().map(|(ch, child)| (ch, child.as_ref()))
+    }
+}
+
+impl Debug for DawgNode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let children = self.children.keys().collect::<Vec<_>>();
+        write!(f, "{{is_word: {}, children: {:?}}}", self.is_word, children)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    const WORDS: [&str; 14] = [
+        "walk", "walks", "walking", "walked", "talk", "talks", "talking", "talked", "jump",
+        "jumps", "jumping", "jumped", "king", "kings",
+    ];
+
+    #[test]
+    fn test_dawg_matches_trie() {
+        let mut trie = WordTrie::default();
+        WORDS.iter().for_each(|word| trie.insert(word));
+        let racks = ["walking", "talked**", "jumps*", "gniklat", "kings*"];
+        let expected = racks.map(|letters| trie.get_words_sorted(letters));
+
+        let dawg = trie.compress();
+
+        for (letters, expected) in racks.iter().zip(expected) {
+            assert_eq!(dawg.get_words_sorted(letters), expected);
+        }
+    }
+
+    #[test]
+    fn test_dawg_has_fewer_nodes() {
+        let mut trie = WordTrie::default();
+        WORDS.iter().for_each(|word| trie.insert(word));
+        let trie_node_count = trie.node_count();
+
+        let dawg = trie.compress();
+
+        assert!(
+            dawg.node_count() < trie_node_count / 2,
+            "expected the DAWG to have less than half of the {} trie nodes, got {}",
+            trie_node_count,
+            dawg.node_count()
+        );
+    }
+
+    #[test]
+    fn test_approx_memory_bytes() {
+        let mut small_trie = WordTrie::default();
+        WORDS[..4].iter().for_each(|word| small_trie.insert(word));
+        let mut trie = WordTrie::default();
+        WORDS.iter().for_each(|word| trie.insert(word));
+
+        assert!(trie.approx_memory_bytes() > small_trie.approx_memory_bytes());
+
+        let trie_bytes = trie.approx_memory_bytes();
+        let dawg = trie.compress();
+
+        assert!(
+            dawg.approx_memory_bytes() < trie_bytes,
+            "expected the DAWG to take up less than the {} bytes of the trie, got {}",
+            trie_bytes,
+            dawg.approx_memory_bytes()
+        );
+    }
+}
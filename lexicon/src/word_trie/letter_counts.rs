@@ -0,0 +1,176 @@
+use super::Alphabet;
+use std::collections::HashMap;
+
+/// The index of the wildcard `*` in [`LetterCounts::Ascii`], after the 26 letters.
+const WILDCARD_SLOT: usize = 26;
+
+/// The number of tiles left of each letter, and of wildcards `*`, in a rack.
+///
+/// Racks of ASCII letters are counted in a fixed-size array so that looking up a letter
+/// while searching doesn't need any hashing. Other letters are counted in a map.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LetterCounts {
+    /// The counts of `a` to `z`, indexed by `ch - 'a'`, then of the wildcard `*`.
+    Ascii([u8; 27]),
+    /// The counts of any letters, without the letters that have no tiles left.
+    Unicode(HashMap<char, usize>),
+}
+
+impl Default for LetterCounts {
+    fn default() -> Self {
+        Self::Ascii([0; 27])
+    }
+}
+
+impl LetterCounts {
+    /// Counts the available letters.
+    ///
+    /// Letters are lowercased the same way as the words inserted into the Trie so a
+    /// letter whose lowercase is multiple characters, like 'İ', adds all of them.
+    pub fn new(letters: &str, alphabet: Alphabet) -> Self {
+        let mut counts = match alphabet {
+            Alphabet::Ascii => Self::default(),
+            Alphabet::Unicode => Self::Unicode(HashMap::new()),
+        };
+
+        for ch in letters.chars() {
+            if alphabet.is_letter(ch) || ch == '*' {
+                ch.to_lowercase().for_each(|ch| counts.add(ch));
+            }
+        }
+
+        counts
+    }
+
+    /// Returns the number of tiles left of the letter.
+    pub fn get(&self, ch: char) -> usize {
+        match self {
+            Self::Ascii(counts) => slot(ch).map_or(0, |i| usize::from(counts[i])),
+            Self::Unicode(counts) => counts.get(&ch).copied().unwrap_or_default(),
+        }
+    }
+
+    /// Returns `true` if there are tiles left of the letter.
+    pub fn contains(&self, ch: char) -> bool {
+        self.get(ch) > 0
+    }
+
+    /// Iterates over the letters that have tiles left together with their counts.
+    pub fn iter(&self) -> impl Iterator<Item = (char, usize)> + '_ {
+        let (ascii, unicode) = match self {
+            Self::Ascii(counts) => (Some(counts), None),
+            Self::Unicode(counts) => (None, Some(counts)),
+        };
+
+        let ascii = ascii.into_iter().flat_map(|counts| {
+            counts
+                .iter()
+                .enumerate()
+                .map(|(i, count)| (slot_char(i), usize::from(*count)))
+        });
+        let unicode = unicode
+            .into_iter()
+            .flatten()
+            .map(|(ch, count)| (*ch, *count));
+
+        ascii.chain(unicode).filter(|(_, count)| *count > 0)
+    }
+
+    /// Adds a tile of the letter.
+    pub fn add(&mut self, ch: char) {
+        self.set(ch, self.get(ch) + 1);
+    }
+
+    /// Uses up a tile of the letter, if there are any left.
+    pub fn take(&mut self, ch: char) {
+        if let Some(count) = self.get(ch).checked_sub(1) {
+            self.set(ch, count);
+        }
+    }
+
+    /// Sets the number of tiles of the letter.
+    ///
+    /// The counts are moved to a map once a letter isn't ASCII or has too many tiles to
+    /// fit in the array.
+    pub fn set(&mut self, ch: char, count: usize) {
+        if let Self::Ascii(counts) = self {
+            match (slot(ch), u8::try_from(count)) {
+                (Some(i), Ok(count)) => {
+                    counts[i] = count;
+                    return;
+                }
+                _ => *self = Self::Unicode(self.iter().collect()),
+            }
+        }
+
+        if let Self::Unicode(counts) = self {
+            if count == 0 {
+                counts.remove(&ch);
+            } else {
+                counts.insert(ch, count);
+            }
+        }
+    }
+}
+
+/// Returns the index of the letter in [`LetterCounts::Ascii`], if it has one.
+fn slot(ch: char) -> Option<usize> {
+    match ch {
+        'a'..='z' => Some(usize::from(ch as u8 - b'a')),
+        '*' => Some(WILDCARD_SLOT),
+        _ => None,
+    }
+}
+
+/// Returns the letter counted at the index of [`LetterCounts::Ascii`].
+fn slot_char(i: usize) -> char {
+    if i == WILDCARD_SLOT {
+        '*'
+    } else {
+        char::from(b'a' + i as u8)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_ascii_counts() {
+        let mut counts = LetterCounts::new("Radar**-", Alphabet::Ascii);
+
+        assert!(matches!(counts, LetterCounts::Ascii(_)));
+        assert_eq!(
+            counts.iter().collect::<Vec<_>>(),
+            [('a', 2), ('d', 1), ('r', 2), ('*', 2)]
+        );
+
+        counts.take('*');
+        counts.take('z');
+        counts.add('z');
+        assert_eq!(counts.get('*'), 1);
+        assert_eq!(counts.get('z'), 1);
+        assert_eq!(counts.get('-'), 0);
+    }
+
+    #[test]
+    fn test_counts_move_to_a_map() {
+        let mut counts = LetterCounts::new("ab*", Alphabet::Ascii);
+        counts.set('*', usize::from(u8::MAX) + 1);
+
+        assert_eq!(
+            counts,
+            LetterCounts::Unicode(HashMap::from([('a', 1), ('b', 1), ('*', 256)]))
+        );
+
+        let mut counts = LetterCounts::new("ab", Alphabet::Ascii);
+        counts.add('é');
+        counts.take('a');
+
+        assert_eq!(
+            counts,
+            LetterCounts::Unicode(HashMap::from([('b', 1), ('é', 1)]))
+        );
+    }
+}
@@ -1,36 +1,178 @@
-use super::Path;
-use std::collections::HashMap;
+use super::{ChildMap, LetterCounts, Path};
 use std::fmt::Debug;
 
 #[derive(Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Node {
-    pub children: HashMap<char, Node>,
+    pub children: ChildMap<Node>,
     pub is_word: bool,
+    /// The number of times the word ending at this node was inserted.
+    pub count: u32,
+    /// The word ending at this node as it was inserted, if it wasn't all lowercase.
+    pub original: Option<Box<str>>,
 }
 
 impl Node {
-    /// Append a chain of child nodes and set the last node as a word.
+    /// Append a chain of child nodes and set the last node as a word, counting how many
+    /// times the word was appended.
+    ///
+    /// The word's casing is kept unless it is all lowercase. Once the word was appended
+    /// in lowercase, its casing is dropped for good, e.g. "polish" wins over "Polish".
     pub fn append_word(&mut self, word: &str) {
-        let last_node = word.to_lowercase().chars().fold(self, |node, ch| {
-            let new_child = node.children.entry(ch).or_default();
+        let lowercase = word.to_lowercase();
+        let last_node = lowercase.chars().fold(self, |node, ch| {
+            let new_child = node.children.get_or_insert_default(ch);
             new_child
         });
+        let appended_in_lowercase = last_node.is_word && last_node.original.is_none();
         last_node.is_word = true;
+        last_node.count = last_node.count.saturating_add(1);
+        last_node.original = if lowercase == word || appended_in_lowercase {
+            None
+        } else {
+            Some(word.into())
+        };
+    }
+
+    /// Gets the node at the end of the chain of child nodes spelling the given word.
+    pub fn find(&self, word: &str) -> Option<&Node> {
+        word.chars()
+            .try_fold(self, |node, ch| node.children.get(&ch))
+    }
+
+    /// Returns the approximate number of bytes allocated by this node for its children
+    /// and original word, and by all of the nodes under it.
+    pub fn allocated_bytes(&self) -> usize {
+        let original = self.original.as_ref().map_or(0, |original| original.len());
+        let children = self
+            .children
+            .values()
+            .map(Node::allocated_bytes)
+            .sum::<usize>();
+
+        self.children.allocated_bytes() + original + children
+    }
+
+    /// Collects every word found under this node, each prefixed by the `word_buf`.
+    pub fn collect_words(&self, word_buf: &mut String, words: &mut Vec<String>) {
+        if self.is_word {
+            words.push(word_buf.clone());
+        }
+
+        for (ch, child) in self.children.iter() {
+            word_buf.push(*ch);
+            child.collect_words(word_buf, words);
+            word_buf.pop();
+        }
+    }
+
+    /// Collects every word under this node that contains the letters of `subseq` in
+    /// order, each prefixed by the `word_buf` which already contains the first
+    /// `matched` letters of it.
+    pub fn collect_with_subsequence(
+        &self,
+        subseq: &[char],
+        matched: usize,
+        word_buf: &mut String,
+        words: &mut Vec<String>,
+    ) {
+        // Matching each letter as early as possible leaves the most room for the rest
+        if matched == subseq.len() {
+            self.collect_words(word_buf, words);
+            return;
+        }
+
+        for (ch, child) in self.children.iter() {
+            let matched = matched + usize::from(*ch == subseq[matched]);
+            word_buf.push(*ch);
+            child.collect_with_subsequence(subseq, matched, word_buf, words);
+            word_buf.pop();
+        }
+    }
+
+    /// Collects the words under this node, reached through `ch`, that are at most
+    /// `max_distance` edits away from the `target`.
+    ///
+    /// The `prev_row` holds the edit distances between the `word_buf` and each prefix
+    /// of the `target`, so only one new row is computed per node. A branch is pruned
+    /// once every distance in its row is over `max_distance` since adding letters can
+    /// never lower them.
+    pub fn collect_fuzzy(
+        &self,
+        ch: char,
+        target: &[char],
+        prev_row: &[usize],
+        max_distance: usize,
+        word_buf: &mut String,
+        words: &mut Vec<(String, usize)>,
+    ) {
+        let mut row = Vec::with_capacity(prev_row.len());
+        row.push(prev_row[0] + 1);
+        for (i, target_ch) in target.iter().enumerate() {
+            let substitution = prev_row[i] + usize::from(*target_ch != ch);
+            let insertion = row[i] + 1;
+            let deletion = prev_row[i + 1] + 1;
+            row.push(substitution.min(insertion).min(deletion));
+        }
+
+        word_buf.push(ch);
+
+        let distance = row[target.len()];
+        if self.is_word && distance <= max_distance {
+            words.push((word_buf.clone(), distance));
+        }
+
+        if row.iter().min().is_some_and(|min| *min <= max_distance) {
+            for (ch, child) in self.children.iter() {
+                child.collect_fuzzy(*ch, target, &row, max_distance, word_buf, words);
+            }
+        }
+
+        word_buf.pop();
     }
+}
+
+/// A node that can be searched through using a [`Path`].
+pub trait TrieNode: Sized {
+    /// Returns `true` if the path leading to this node spells a word.
+    fn is_word(&self) -> bool;
+
+    /// Gets the child node reached through the given [`char`].
+    fn child(&self, ch: &char) -> Option<&Self>;
+
+    /// Iterates over the child nodes together with the [`char`] leading to them, in
+    /// alphabetical order.
+    fn children(&self) -> impl DoubleEndedIterator<Item = (&char, &Self)>;
 
     /// Create a new [`Path`] starting from this node.
-    pub fn start_path(&self, remaining_letters: HashMap<char, usize>) -> Path {
+    fn start_path(&self, remaining_letters: LetterCounts) -> Path<'_, Self> {
         Path {
             node: self,
             remaining_letters,
             word_buf: String::new(),
+            wildcards: Vec::new(),
+            unlimited: false,
         }
     }
 }
 
+impl TrieNode for Node {
+    fn is_word(&self) -> bool {
+        self.is_word
+    }
+
+    fn child(&self, ch: &char) -> Option<&Self> {
+        self.children.get(ch)
+    }
+
+    fn children(&self) -> impl DoubleEndedIterator<Item = (&char, &Self)> {
+        self.children.iter()
+    }
+}
+
 impl Debug for Node {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let children = self.children.keys();
+        let children = self.children.keys().collect::<Vec<_>>();
         write!(f, "{{is_word: {}, children: {:?}}}", self.is_word, children)
     }
 }
@@ -48,26 +190,49 @@ mod test {
 
         let expected = Node {
             is_word: false,
-            children: HashMap::from([(
+            children: ChildMap::from([(
                 'c',
                 Node {
-                    children: HashMap::from([(
+                    children: ChildMap::from([(
                         'a',
                         Node {
-                            children: HashMap::from([(
+                            children: ChildMap::from([(
                                 'r',
                                 Node {
-                                    children: HashMap::new(),
+                                    children: ChildMap::default(),
                                     is_word: true,
+                                    count: 1,
+                                    original: None,
                                 },
                             )]),
                             is_word: false,
+                            count: 0,
+                            original: None,
                         },
                     )]),
                     is_word: false,
+                    count: 0,
+                    original: None,
                 },
             )]),
+            count: 0,
+            original: None,
         };
         assert_eq!(root, expected);
     }
+
+    #[test]
+    fn test_traverse_children_alphabetically() {
+        let mut root = Node::default();
+        ["tac", "cat", "act", "cart", "at"]
+            .iter()
+            .for_each(|word| root.append_word(word));
+
+        let mut words = Vec::new();
+        root.collect_words(&mut String::new(), &mut words);
+
+        assert_eq!(words, ["act", "at", "cart", "cat", "tac"]);
+        assert!(root.find("car").is_some_and(|node| !node.is_word));
+        assert!(root.find("cab").is_none());
+    }
 }
@@ -1,34 +1,84 @@
-use super::Node;
-use std::collections::hash_map::Entry;
-use std::collections::{HashMap, VecDeque};
+use super::{LetterCounts, Node, TrieNode};
+use std::collections::VecDeque;
 use std::fmt::Debug;
 
 #[derive(Debug, PartialEq)]
-pub struct Path<'a> {
-    pub node: &'a Node,
-    pub remaining_letters: HashMap<char, usize>,
+pub struct Path<'a, N = Node> {
+    pub node: &'a N,
+    pub remaining_letters: LetterCounts,
     pub word_buf: String,
+    /// The positions in the `word_buf` that were filled using a wildcard `*`.
+    pub wildcards: Vec<usize>,
+    /// Every letter is available any number of times so the `remaining_letters` are
+    /// ignored.
+    pub unlimited: bool,
 }
 
 /// Steps through one layer of the Trie using the given letters and return
 /// the next possible paths
-pub fn step_trie<'a>(path: &Path<'a>, search_stack: &mut VecDeque<Path<'a>>) {
-    let children = &path.node.children;
+///
+/// The `punctuation` can be stepped through without using any of the letters. A
+/// wildcard `*` is only used for a letter once all of its tiles are used up.
+pub fn step_trie<'a, N: TrieNode>(
+    path: &Path<'a, N>,
+    punctuation: &[char],
+    search_stack: &mut VecDeque<Path<'a, N>>,
+) {
+    let node = path.node;
     let letters = &path.remaining_letters;
 
-    for ch in letters.keys() {
-        // handle wildcard
-        if *ch == '*' {
-            let remaining_letters = letters.clone();
+    if path.unlimited {
+        for (ch, child) in node.children() {
+            let mut word_buf = path.word_buf.clone();
+            word_buf.push(*ch);
+
+            search_stack.push_back(Path {
+                node: child,
+                remaining_letters: letters.clone(),
+                word_buf,
+                wildcards: path.wildcards.clone(),
+                unlimited: true,
+            });
+        }
 
-            let Ok(remaining_letters) = decrement_count(remaining_letters, ch) else {
-                continue;
-            };
+        return;
+    }
 
-            for (ch, child) in children
-                .iter()
-                .filter(|c| !remaining_letters.contains_key(c.0))
+    // handle punctuation
+    for ch in punctuation {
+        if let Some(child) = node.child(ch) {
+            let mut word_buf = path.word_buf.clone();
+            word_buf.push(*ch);
+
+            search_stack.push_back(Path {
+                node: child,
+                remaining_letters: letters.clone(),
+                word_buf,
+                wildcards: path.wildcards.clone(),
+                unlimited: false,
+            });
+        }
+    }
+
+    for (ch, _) in letters.iter() {
+        // handle wildcard
+        if ch == '*' {
+            let mut remaining_letters = letters.clone();
+            remaining_letters.take(ch);
+
+            // A wildcard can become any letter, including one that is in the rack, but
+            // only once every tile of that letter is used up. Using the tiles first finds
+            // the same words while keeping each word from being found once per way of
+            // spending the wildcards, e.g. "aa*" finds "aaa" with the wildcard last.
+            // Since every letter of a word is either a tile or a wildcard, never both,
+            // each word is found exactly once no matter how many wildcards there are.
+            for (ch, child) in node
+                .children()
+                .filter(|c| !remaining_letters.contains(*c.0) && !punctuation.contains(c.0))
             {
+                let mut wildcards = path.wildcards.clone();
+                wildcards.push(path.word_buf.chars().count());
+
                 let mut word_buf = path.word_buf.clone();
                 word_buf.push(*ch);
 
@@ -36,6 +86,8 @@ pub fn step_trie<'a>(path: &Path<'a>, search_stack: &mut VecDeque<Path<'a>>) {
                     node: child,
                     remaining_letters: remaining_letters.clone(),
                     word_buf,
+                    wildcards,
+                    unlimited: false,
                 });
             }
 
@@ -43,43 +95,28 @@ pub fn step_trie<'a>(path: &Path<'a>, search_stack: &mut VecDeque<Path<'a>>) {
         }
 
         // handle non-wildcard
-        if let Some(child) = children.get(ch) {
-            let remaining_letters = letters.clone();
-
-            let Ok(remaining_letters) = decrement_count(remaining_letters, ch) else {
-                continue;
-            };
+        if let Some(child) = node.child(&ch) {
+            let mut remaining_letters = letters.clone();
+            remaining_letters.take(ch);
 
             let mut word_buf = path.word_buf.clone();
-            word_buf.push(*ch);
+            word_buf.push(ch);
 
             search_stack.push_back(Path {
                 node: child,
                 remaining_letters,
                 word_buf,
+                wildcards: path.wildcards.clone(),
+                unlimited: false,
             });
         }
     }
 }
 
-fn decrement_count(
-    mut counts: HashMap<char, usize>,
-    ch: &char,
-) -> Result<HashMap<char, usize>, ()> {
-    let Entry::Occupied(mut ch_entry) = counts.entry(*ch) else {
-        return Err(());
-    };
-    if *ch_entry.get() <= 1 {
-        ch_entry.remove();
-    } else {
-        *ch_entry.get_mut() -= 1;
-    }
-    Ok(counts)
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::word_trie::Alphabet;
 
     #[test]
     fn test_step_trie() {
@@ -89,17 +126,21 @@ mod test {
 
         let initial_path = Path {
             node: &root,
-            remaining_letters: HashMap::from([('c', 1), ('a', 1), ('*', 1)]),
+            remaining_letters: LetterCounts::new("ca*", Alphabet::Ascii),
             word_buf: "".to_string(),
+            wildcards: vec![],
+            unlimited: false,
         };
 
         let mut paths = VecDeque::new();
-        step_trie(&initial_path, &mut paths);
+        step_trie(&initial_path, &[], &mut paths);
 
         let expected_paths = [Path {
             node: root.children.get(&'c').unwrap(),
-            remaining_letters: HashMap::from([('a', 1), ('*', 1)]),
+            remaining_letters: LetterCounts::new("a*", Alphabet::Ascii),
             word_buf: "c".to_string(),
+            wildcards: vec![],
+            unlimited: false,
         }];
         for expected_path in expected_paths.iter() {
             assert!(
@@ -110,4 +151,60 @@ mod test {
             );
         }
     }
+
+    #[test]
+    fn test_step_trie_through_punctuation() {
+        let mut root = Node::default();
+        root.append_word("-a");
+
+        let initial_path = Path {
+            node: &root,
+            remaining_letters: LetterCounts::new("*", Alphabet::Ascii),
+            word_buf: "".to_string(),
+            wildcards: vec![],
+            unlimited: false,
+        };
+
+        let mut paths = VecDeque::new();
+        step_trie(&initial_path, &['-'], &mut paths);
+
+        let expected_paths = [Path {
+            node: root.children.get(&'-').unwrap(),
+            remaining_letters: LetterCounts::new("*", Alphabet::Ascii),
+            word_buf: "-".to_string(),
+            wildcards: vec![],
+            unlimited: false,
+        }];
+        assert_eq!(
+            paths, expected_paths,
+            "punctuation should not use up letters nor be filled by a wildcard"
+        );
+    }
+
+    #[test]
+    fn test_step_trie_unlimited() {
+        let mut root = Node::default();
+        root.append_word("aa");
+        root.append_word("b-");
+
+        let initial_path = Path {
+            node: root.children.get(&'a').unwrap(),
+            remaining_letters: LetterCounts::default(),
+            word_buf: "a".to_string(),
+            wildcards: vec![],
+            unlimited: true,
+        };
+
+        let mut paths = VecDeque::new();
+        step_trie(&initial_path, &['-'], &mut paths);
+
+        let expected_paths = [Path {
+            node: root.children[&'a'].children.get(&'a').unwrap(),
+            remaining_letters: LetterCounts::default(),
+            word_buf: "aa".to_string(),
+            wildcards: vec![],
+            unlimited: true,
+        }];
+        assert_eq!(paths, expected_paths);
+    }
 }
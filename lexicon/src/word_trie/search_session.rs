@@ -0,0 +1,343 @@
+use super::{FoundWord, LetterCounts, Path, TrieNode, WordTrie, WordsIter};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// The number of words found between checks of the progress interval.
+const PROGRESS_CHECK_WORDS: usize = 64;
+
+/// Searches a [`WordTrie`] as the rack is typed, reusing the previous search when letters
+/// are only appended to the rack.
+///
+/// Appending a letter can only make more words buildable. The new words are the ones
+/// using more of that letter than before, and each of them branches off a path of the
+/// previous search that had used up every tile of the letter. So the paths that could
+/// step through a letter if they had one more tile of it are kept, and only those paths
+/// are searched further.
+///
+/// Any other edit, like removing a letter, starts a new search. So does a rack with a
+/// wildcard `*` since a new tile can change which letters the wildcards stand for.
+#[derive(Default)]
+pub struct SearchSession {
+    /// The letters of the last search if it can be extended.
+    letters: Option<String>,
+    letters_map: LetterCounts,
+    /// The `word_buf` of the paths that used up every tile of a letter they could step
+    /// through next, by letter.
+    blocked: HashMap<char, Vec<String>>,
+    words: Vec<FoundWord>,
+    /// The number of paths stepped through by the last search.
+    visited: usize,
+}
+
+impl SearchSession {
+    /// Gets all the words that could be built using the given letters, or `None` if the
+    /// search was cancelled by setting the `cancelled` flag to `true`.
+    pub fn search(
+        &mut self,
+        trie: &WordTrie,
+        letters: &str,
+        cancelled: &AtomicBool,
+    ) -> Option<&[FoundWord]> {
+        self.search_with_progress(trie, letters, cancelled, Duration::MAX, &mut |_| {})
+    }
+
+    /// Same as [`SearchSession::search`] but also passes the words found so far to
+    /// `on_progress` about every `interval` while searching.
+    pub fn search_with_progress(
+        &mut self,
+        trie: &WordTrie,
+        letters: &str,
+        cancelled: &AtomicBool,
+        interval: Duration,
+        on_progress: &mut dyn FnMut(&[FoundWord]),
+    ) -> Option<&[FoundWord]> {
+        self.visited = 0;
+        let mut progress = Progress {
+            interval,
+            last_report: Instant::now(),
+            on_progress,
+        };
+
+        let appended = self
+            .letters
+            .as_deref()
+            .and_then(|prev_letters| letters.strip_prefix(prev_letters));
+        let has_wildcard = letters.contains('*');
+
+        let completed = match appended {
+            Some(appended) if !has_wildcard => {
+                self.extend(trie, appended, cancelled, &mut progress)
+            }
+            _ => self.restart(trie, letters, cancelled, &mut progress),
+        };
+
+        // A cancelled search is incomplete so it can't be extended
+        self.letters = (completed && !has_wildcard).then(|| letters.to_string());
+
+        completed.then_some(self.words.as_slice())
+    }
+
+    /// Returns the number of paths stepped through by the last search.
+    pub fn visited(&self) -> usize {
+        self.visited
+    }
+
+    /// Drops the previous search and searches the given letters from scratch.
+    fn restart(
+        &mut self,
+        trie: &WordTrie,
+        letters: &str,
+        cancelled: &AtomicBool,
+        progress: &mut Progress,
+    ) -> bool {
+        self.letters_map = LetterCounts::new(letters, trie.alphabet);
+        self.blocked.clear();
+        self.words.clear();
+
+        let start_path = trie.root.start_path(self.letters_map.clone());
+        self.explore(trie, start_path, cancelled, progress)
+    }
+
+    /// Extends the previous search with the appended letters, one letter at a time.
+    fn extend(
+        &mut self,
+        trie: &WordTrie,
+        appended: &str,
+        cancelled: &AtomicBool,
+        progress: &mut Progress,
+    ) -> bool {
+        for ch in appended.chars() {
+            for (ch, _) in LetterCounts::new(&ch.to_string(), trie.alphabet).iter() {
+                if !self.extend_letter(trie, ch, cancelled, progress) {
+                    return false;
+                }
+            }
+        }
+
+        true
+    }
+
+    fn extend_letter(
+        &mut self,
+        trie: &WordTrie,
+        ch: char,
+        cancelled: &AtomicBool,
+        progress: &mut Progress,
+    ) -> bool {
+        self.letters_map.add(ch);
+
+        // The paths that used up every tile of the letter and can now use one more
+        let resume_from = self
+            .blocked
+            .remove(&ch)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|mut word_buf| {
+                let node = trie.root.find(&word_buf)?.child(&ch)?;
+                word_buf.push(ch);
+
+                Some(Path {
+                    node,
+                    remaining_letters: self.remaining_letters(&word_buf, &trie.punctuation),
+                    word_buf,
+                    wildcards: Vec::new(),
+                    unlimited: false,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        resume_from
+            .into_iter()
+            .all(|path| self.explore(trie, path, cancelled, progress))
+    }
+
+    /// Returns the letters that are left after building the `word_buf`.
+    fn remaining_letters(&self, word_buf: &str, punctuation: &[char]) -> LetterCounts {
+        let mut remaining = self.letters_map.clone();
+
+        for ch in word_buf.chars().filter(|ch| !punctuation.contains(ch)) {
+            remaining.take(ch);
+        }
+
+        remaining
+    }
+
+    /// Searches every path reachable from the `start_path`, keeping the words found and
+    /// the paths blocked by a used up letter. Returns `false` if the search was
+    /// cancelled.
+    fn explore(
+        &mut self,
+        trie: &WordTrie,
+        start_path: Path<'_>,
+        cancelled: &AtomicBool,
+        progress: &mut Progress,
+    ) -> bool {
+        let mut words = WordsIter::new(start_path, &trie.punctuation).cancellable(cancelled);
+        if !self.letters_map.contains('*') {
+            words = words.record_blocked();
+        }
+
+        let found_words = &mut self.words;
+        let exhausted = words.search(|found| {
+            found_words.push(found);
+
+            if found_words.len().is_multiple_of(PROGRESS_CHECK_WORDS) {
+                progress.report_if_due(found_words);
+            }
+        });
+
+        for words in exhausted {
+            self.visited += words.visited;
+            for (ch, word_buf) in words.blocked.into_iter().flatten() {
+                self.blocked.entry(ch).or_default().push(word_buf);
+            }
+        }
+
+        !cancelled.load(Ordering::Relaxed)
+    }
+}
+
+/// Reports the words found so far while searching.
+struct Progress<'a> {
+    interval: Duration,
+    last_report: Instant,
+    on_progress: &'a mut dyn FnMut(&[FoundWord]),
+}
+
+impl Progress<'_> {
+    fn report_if_due(&mut self, words: &[FoundWord]) {
+        if self.last_report.elapsed() >= self.interval {
+            (self.on_progress)(words);
+            self.last_report = Instant::now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::path::Path;
+
+    fn sorted_words(words: Option<&[FoundWord]>) -> Vec<String> {
+        let mut words = words
+            .expect("search is not cancelled")
+            .iter()
+            .map(|found| found.word.clone())
+            .collect::<Vec<_>>();
+        words.sort();
+        words
+    }
+
+    #[test]
+    fn test_incremental_search_matches_fresh_search() {
+        let trie = WordTrie::new_from_file(Path::new("./test_data/words.txt")).unwrap();
+        let cancelled = AtomicBool::new(false);
+        let mut session = SearchSession::default();
+
+        // typing, pasting several letters at once, deleting, then wildcards
+        let racks = [
+            "r",
+            "ra",
+            "rad",
+            "rada",
+            "radar",
+            "radarti",
+            "radartic",
+            "radarti",
+            "radartio",
+            "radartion",
+            "radartion*",
+            "radartion**",
+            "c",
+            "ca",
+            "car",
+            "cart",
+        ];
+        for letters in racks {
+            assert_eq!(
+                sorted_words(session.search(&trie, letters, &cancelled)),
+                trie.get_words_sorted(letters),
+                "{letters:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_appending_searches_less() {
+        let trie = WordTrie::new_from_file(Path::new("./test_data/words.txt")).unwrap();
+        let cancelled = AtomicBool::new(false);
+        let mut session = SearchSession::default();
+
+        session.search(&trie, "radical", &cancelled);
+        session.search(&trie, "radicalt", &cancelled);
+        let incremental_visited = session.visited();
+
+        let mut fresh_session = SearchSession::default();
+        fresh_session.search(&trie, "radicalt", &cancelled);
+
+        assert!(incremental_visited < fresh_session.visited());
+    }
+
+    #[test]
+    fn test_only_blocked_paths_are_kept() {
+        let trie = WordTrie::new_from_file(Path::new("./test_data/words.txt")).unwrap();
+        let cancelled = AtomicBool::new(false);
+        let mut session = SearchSession::default();
+
+        session.search(&trie, "radicalt", &cancelled);
+        let kept = session.blocked.values().map(Vec::len).sum::<usize>();
+
+        assert!(kept < session.visited(), "kept {kept} paths");
+        assert!(session.blocked.iter().all(|(ch, word_bufs)| {
+            word_bufs.iter().all(|word_buf| {
+                let held = "radicalt".chars().filter(|c| c == ch).count();
+                word_buf.chars().filter(|c| c == ch).count() == held
+            })
+        }));
+    }
+
+    #[test]
+    fn test_search_reports_progress() {
+        // Every word of up to four letters made out of "abcde"
+        let mut trie = WordTrie::default();
+        let mut words = vec![String::new()];
+        for _ in 0..4 {
+            words = words
+                .iter()
+                .flat_map(|word| "abcde".chars().map(move |ch| format!("{word}{ch}")))
+                .collect();
+            words.iter().for_each(|word| trie.insert(word));
+        }
+        let cancelled = AtomicBool::new(false);
+        let mut session = SearchSession::default();
+        let mut reported = Vec::new();
+
+        let words =
+            session.search_with_progress(&trie, "****", &cancelled, Duration::ZERO, &mut |words| {
+                reported.push(words.len())
+            });
+        let found = words.expect("search is not cancelled").len();
+
+        assert!(!reported.is_empty());
+        assert!(reported.windows(2).all(|counts| counts[0] <= counts[1]));
+        assert!(reported.iter().all(|count| *count <= found));
+    }
+
+    #[test]
+    fn test_cancelled_search_restarts() {
+        let trie = WordTrie::new_from_file(Path::new("./test_data/words.txt")).unwrap();
+        let mut session = SearchSession::default();
+
+        assert!(session
+            .search(&trie, "rad", &AtomicBool::new(true))
+            .is_none());
+
+        let cancelled = AtomicBool::new(false);
+        assert_eq!(
+            sorted_words(session.search(&trie, "rada", &cancelled)),
+            trie.get_words_sorted("rada")
+        );
+    }
+}
@@ -0,0 +1,373 @@
+#[cfg(doc)]
+use super::step_trie;
+use super::{LetterCounts, Node, Path, TrieNode};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// A word found while searching through the Trie.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FoundWord {
+    pub word: String,
+    /// The positions in the `word` that were filled using a wildcard `*`.
+    pub wildcards: Vec<usize>,
+}
+
+impl FoundWord {
+    /// Returns the rack tiles used to build the word, in the order they are placed.
+    ///
+    /// The `punctuation` in the word doesn't use up any tile so it is skipped.
+    pub fn tiles(&self, punctuation: &[char]) -> Vec<Tile> {
+        self.word
+            .chars()
+            .enumerate()
+            .filter(|(_, ch)| !punctuation.contains(ch))
+            .map(|(i, ch)| {
+                if self.wildcards.contains(&i) {
+                    Tile::Wildcard(ch)
+                } else {
+                    Tile::Letter(ch)
+                }
+            })
+            .collect()
+    }
+}
+
+/// A rack tile used to build a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    /// A tile of the given letter.
+    Letter(char),
+    /// A wildcard `*` standing in for the given letter.
+    Wildcard(char),
+}
+
+/// Lazily yields the words found while searching through the Trie.
+///
+/// Each call to [`Iterator::next`] only advances the search until the next word is
+/// found so the caller can stop the search early.
+///
+/// The search goes depth-first through a single set of remaining letters and a single
+/// word buffer, undoing each step once its branch is searched, instead of cloning them
+/// for every branch like [`step_trie`] does.
+pub struct WordsIter<'a, N = Node> {
+    /// The steps left to take, including those undoing the steps already taken.
+    stack: Vec<Step<'a, N>>,
+    remaining_letters: LetterCounts,
+    word_buf: String,
+    /// The number of characters in the `word_buf`.
+    depth: usize,
+    wildcards: Vec<usize>,
+    unlimited: bool,
+    /// The characters the words must start with.
+    prefix: Vec<char>,
+    /// The punctuation that can be stepped through without using any letters.
+    punctuation: &'a [char],
+    /// The number of paths stepped through so far.
+    pub visited: usize,
+    /// Stops the search once set to `true`.
+    pub cancelled: Option<&'a AtomicBool>,
+    /// The letters and `word_buf` of the paths that used up every tile of a letter they
+    /// could step through next, if they are recorded.
+    pub blocked: Option<Vec<(char, String)>>,
+}
+
+/// A step of the search through a [`WordsIter`].
+enum Step<'a, N> {
+    /// Steps into the node through the character, if any, using up the tile.
+    Enter {
+        node: &'a N,
+        ch: Option<char>,
+        tile: TileUse,
+    },
+    /// Steps back out of the node reached through the character, giving back the tile.
+    Leave { tile: TileUse },
+}
+
+/// The rack tile used up to step through a character.
+#[derive(Clone, Copy)]
+enum TileUse {
+    /// Punctuation, or any letter if the letters are unlimited
+    Free,
+    Letter(char),
+    Wildcard,
+}
+
+impl<'a, N: TrieNode> WordsIter<'a, N> {
+    pub fn new(start_path: Path<'a, N>, punctuation: &'a [char]) -> Self {
+        Self {
+            stack: vec![Step::Enter {
+                node: start_path.node,
+                ch: None,
+                tile: TileUse::Free,
+            }],
+            depth: start_path.word_buf.chars().count(),
+            remaining_letters: start_path.remaining_letters,
+            word_buf: start_path.word_buf,
+            wildcards: start_path.wildcards,
+            unlimited: start_path.unlimited,
+            prefix: Vec::new(),
+            punctuation,
+            visited: 0,
+            cancelled: None,
+            blocked: None,
+        }
+    }
+
+    /// Records the paths blocked by a used up letter in [`WordsIter::blocked`] so the
+    /// search can be resumed from them once more tiles of the letter are added.
+    pub fn record_blocked(mut self) -> Self {
+        self.blocked = Some(Vec::new());
+        self
+    }
+
+    /// Stops the search as soon as the flag is set to `true`, even if there are
+    /// branches left to search.
+    pub fn cancellable(mut self, cancelled: &'a AtomicBool) -> Self {
+        self.cancelled = Some(cancelled);
+        self
+    }
+
+    /// Only steps through the paths spelling the given prefix so that only the words
+    /// starting with the prefix are yielded.
+    pub fn with_prefix(start_path: Path<'a, N>, punctuation: &'a [char], prefix: &str) -> Self {
+        let mut iter = Self::new(start_path, punctuation);
+        iter.prefix = prefix.chars().collect();
+        iter
+    }
+
+    /// Returns `true` once there are no branches left to search.
+    #[cfg(test)]
+    pub fn is_exhausted(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// Steps into the node through the character and returns `true` if the node spells
+    /// a word, after pushing the steps to take from it.
+    fn enter(&mut self, node: &'a N, ch: Option<char>, tile: TileUse) -> bool {
+        if let Some(ch) = ch {
+            match tile {
+                TileUse::Free => {}
+                TileUse::Letter(letter) => self.remaining_letters.take(letter),
+                TileUse::Wildcard => {
+                    self.remaining_letters.take('*');
+                    self.wildcards.push(self.depth);
+                }
+            }
+            self.word_buf.push(ch);
+            self.depth += 1;
+            self.stack.push(Step::Leave { tile });
+        }
+
+        self.push_children(node);
+        node.is_word() && self.depth >= self.prefix.len()
+    }
+
+    /// Undoes the last step into a node.
+    fn leave(&mut self, tile: TileUse) {
+        self.word_buf.pop();
+        self.depth -= 1;
+        match tile {
+            TileUse::Free => {}
+            TileUse::Letter(letter) => self.remaining_letters.add(letter),
+            TileUse::Wildcard => {
+                self.remaining_letters.add('*');
+                self.wildcards.pop();
+            }
+        }
+    }
+
+    /// Pushes the steps into the children of the node that the remaining letters allow,
+    /// the same ones as [`step_trie`].
+    fn push_children(&mut self, node: &'a N) {
+        let required = self.prefix.get(self.depth).copied();
+        let allowed = |ch: &char| required.is_none_or(|required| required == *ch);
+        let push = |stack: &mut Vec<_>, ch: char, node, tile| {
+            if allowed(&ch) {
+                stack.push(Step::Enter {
+                    node,
+                    ch: Some(ch),
+                    tile,
+                });
+            }
+        };
+
+        // The last child pushed is searched first so the words come out in
+        // alphabetical order
+        if self.unlimited {
+            for (ch, child) in node.children().rev() {
+                push(&mut self.stack, *ch, child, TileUse::Free);
+            }
+            return;
+        }
+
+        for ch in self.punctuation {
+            if let Some(child) = node.child(ch) {
+                push(&mut self.stack, *ch, child, TileUse::Free);
+            }
+        }
+
+        for (letter, _) in self.remaining_letters.iter() {
+            // A wildcard only stands in for the letters without tiles left, see
+            // `step_trie`
+            if letter == '*' {
+                for (ch, child) in node.children() {
+                    if !self.remaining_letters.contains(*ch) && !self.punctuation.contains(ch) {
+                        push(&mut self.stack, *ch, child, TileUse::Wildcard);
+                    }
+                }
+                continue;
+            }
+
+            if let Some(child) = node.child(&letter) {
+                push(&mut self.stack, letter, child, TileUse::Letter(letter));
+            }
+        }
+
+        if let Some(blocked) = &mut self.blocked {
+            for (ch, _) in node.children() {
+                if !self.remaining_letters.contains(*ch) && !self.punctuation.contains(ch) {
+                    blocked.push((*ch, self.word_buf.clone()));
+                }
+            }
+        }
+    }
+}
+
+impl<N: TrieNode> Iterator for WordsIter<'_, N> {
+    type Item = FoundWord;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(step) = self.stack.pop() {
+            if self
+                .cancelled
+                .is_some_and(|cancelled| cancelled.load(Ordering::Relaxed))
+            {
+                self.stack.clear();
+                return None;
+            }
+
+            match step {
+                Step::Leave { tile } => self.leave(tile),
+                Step::Enter { node, ch, tile } => {
+                    self.visited += 1;
+                    if self.enter(node, ch, tile) {
+                        return Some(FoundWord {
+                            word: self.word_buf.clone(),
+                            wildcards: self.wildcards.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a> WordsIter<'a> {
+    /// Searches every word left, passing each one to `on_found`, then returns the
+    /// exhausted iterators the search was split into so that their `visited` counts and
+    /// `blocked` paths can be read.
+    ///
+    /// With the `parallel` feature, the branches of the first search layer are searched
+    /// in parallel while `on_found` is still called on the calling thread, in no
+    /// particular order.
+    #[cfg(not(feature = "parallel"))]
+    pub fn search(mut self, mut on_found: impl FnMut(FoundWord)) -> Vec<Self> {
+        self.by_ref().for_each(&mut on_found);
+        vec![self]
+    }
+
+    /// Searches every word left, passing each one to `on_found`, then returns the
+    /// exhausted iterators the search was split into so that their `visited` counts and
+    /// `blocked` paths can be read.
+    ///
+    /// With the `parallel` feature, the branches of the first search layer are searched
+    /// in parallel while `on_found` is still called on the calling thread, in no
+    /// particular order.
+    #[cfg(feature = "parallel")]
+    pub fn search(mut self, mut on_found: impl FnMut(FoundWord)) -> Vec<Self> {
+        use rayon::prelude::*;
+        use std::sync::mpsc;
+
+        /// What a branch searched in parallel sends back.
+        enum Searched<'a> {
+            Found(FoundWord),
+            Exhausted(WordsIter<'a>),
+        }
+
+        let (found, branches) = self.split();
+        found.into_iter().for_each(&mut on_found);
+
+        let (tx, rx) = mpsc::channel();
+        let mut exhausted = vec![self];
+        std::thread::scope(|scope| {
+            scope.spawn(move || {
+                branches
+                    .into_par_iter()
+                    .for_each_with(tx, |tx, mut branch| {
+                        for found in branch.by_ref() {
+                            let _ = tx.send(Searched::Found(found));
+                        }
+                        let _ = tx.send(Searched::Exhausted(branch));
+                    });
+            });
+
+            for searched in rx {
+                match searched {
+                    Searched::Found(found) => on_found(found),
+                    Searched::Exhausted(branch) => exhausted.push(branch),
+                }
+            }
+        });
+
+        exhausted
+    }
+
+    /// Collects every word left, searching like [`WordsIter::search`].
+    pub fn collect_found(self) -> Vec<FoundWord> {
+        let mut words = Vec::new();
+        self.search(|found| words.push(found));
+        words
+    }
+
+    /// Steps into the start node, then moves each branch left to search into its own
+    /// iterator. Returns the word spelled by the start node, if any, with the branches.
+    ///
+    /// The iterator must not have been advanced yet.
+    #[cfg(feature = "parallel")]
+    pub fn split(&mut self) -> (Option<FoundWord>, Vec<Self>) {
+        let mut found = None;
+        if let Some(Step::Enter { node, ch, tile }) = self.stack.pop() {
+            self.visited += 1;
+            if self.enter(node, ch, tile) {
+                found = Some(FoundWord {
+                    word: self.word_buf.clone(),
+                    wildcards: self.wildcards.clone(),
+                });
+            }
+        }
+
+        // The last step on the stack is searched first so the branches are kept in the
+        // order they would have been searched
+        let steps = std::mem::take(&mut self.stack);
+        let branches = steps
+            .into_iter()
+            .rev()
+            .map(|step| Self {
+                stack: vec![step],
+                remaining_letters: self.remaining_letters.clone(),
+                word_buf: self.word_buf.clone(),
+                depth: self.depth,
+                wildcards: self.wildcards.clone(),
+                unlimited: self.unlimited,
+                prefix: self.prefix.clone(),
+                punctuation: self.punctuation,
+                visited: 0,
+                cancelled: self.cancelled,
+                blocked: self.blocked.as_ref().map(|_| Vec::new()),
+            })
+            .collect();
+
+        (found, branches)
+    }
+}
@@ -1,8 +1,18 @@
+mod history;
 mod panel_manager;
+mod spinner;
 
+use history::History;
 pub use panel_manager::*;
+use spinner::Spinner;
 
+use crate::config::{KeyBindings, ResultFormat, Theme};
+use crate::search_worker::QueryRequest;
+use crate::session::Session;
 use regex::Regex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 #[derive(Default)]
 pub struct AppManager {
@@ -13,13 +23,17 @@ impl AppManager {
     /// Push a [`char`] to the input field of current panel if it has one and return
     /// `true` if the state was updated.
     pub fn push_ch(&mut self, ch: char) -> bool {
-        if ch.is_whitespace() {
+        if ch.is_whitespace() || self.state.show_help {
             return false;
         }
 
         match self.state.selected_panel.kind() {
             PanelKind::Letters => self.state.input_letters.push(ch),
-            PanelKind::Regex => self.state.input_regex.push(ch),
+            PanelKind::Regex => {
+                self.state.input_regex.push(ch);
+                self.update_regex_error();
+            }
+            PanelKind::Length => self.state.input_length.push(ch),
             PanelKind::Words => return false,
         }
 
@@ -29,13 +43,143 @@ impl AppManager {
     /// Pops a [`char`] to the input field of current panel if it has one and return
     /// `true` if the state was updated.
     pub fn pop_ch(&mut self) -> bool {
+        if self.state.show_help {
+            return false;
+        }
+
         match self.state.selected_panel.kind() {
             PanelKind::Letters => self.state.input_letters.pop().is_some(),
-            PanelKind::Regex => self.state.input_regex.pop().is_some(),
+            PanelKind::Regex => {
+                let popped = self.state.input_regex.pop().is_some();
+                self.update_regex_error();
+                popped
+            }
+            PanelKind::Length => self.state.input_length.pop().is_some(),
             _ => false,
         }
     }
 
+    /// Clears the input of the selected panel, returning `true` if it wasn't already
+    /// empty.
+    pub fn clear_input(&mut self) -> bool {
+        self.edit_input(|input| input.clear())
+    }
+
+    /// Returns the rack and regex to restore at the next start.
+    pub fn session(&self) -> Session {
+        Session {
+            letters: self.state.input_letters.clone(),
+            regex: self.state.input_regex.clone(),
+        }
+    }
+
+    /// Restores the rack and regex of the last run.
+    pub fn restore_session(&mut self, session: Session) {
+        self.state.input_letters = session.letters;
+        self.state.input_regex = session.regex;
+        self.update_regex_error();
+    }
+
+    /// Remembers the rack in the Letters panel so it can be recalled later.
+    pub fn push_history(&mut self) {
+        self.state.history.push(&self.state.input_letters);
+    }
+
+    /// Replaces the rack in the Letters panel with an older rack when going up or a
+    /// newer one when going down, returning `true` if the rack was replaced.
+    pub fn recall_history(&mut self, direction: Direction) -> bool {
+        if self.state.show_help || self.state.selected_panel.kind() != PanelKind::Letters {
+            return false;
+        }
+
+        let history = &mut self.state.history;
+        let rack = match direction {
+            Direction::Up => history.older(&self.state.input_letters),
+            Direction::Down => history.newer(),
+            Direction::Left | Direction::Right => None,
+        };
+        let Some(rack) = rack else {
+            return false;
+        };
+
+        self.state.input_letters = rack.to_string();
+        true
+    }
+
+    /// Empties every input and the words found, returning `true` if anything was
+    /// cleared.
+    pub fn clear_all(&mut self) -> bool {
+        let state = &mut self.state;
+        let cleared = !(state.input_letters.is_empty()
+            && state.input_regex.is_empty()
+            && state.input_length.is_empty()
+            && state.output_words.is_empty());
+
+        state.input_letters.clear();
+        state.input_regex.clear();
+        state.input_length.clear();
+        state.output_words.clear();
+        state.selected_word = 0;
+        state.query_elapsed = None;
+        state.search_partial = false;
+        self.update_regex_error();
+        cleared
+    }
+
+    /// Deletes the last word of the input of the selected panel, or its last character
+    /// if that isn't part of a word, like `(` in a regex. Returns `true` if anything was
+    /// deleted.
+    pub fn delete_word(&mut self) -> bool {
+        self.edit_input(|input| {
+            let word_start = input
+                .char_indices()
+                .rev()
+                .take_while(|(_, ch)| ch.is_alphanumeric())
+                .last()
+                .map(|(i, _)| i);
+            match word_start {
+                Some(i) => input.truncate(i),
+                None => {
+                    input.pop();
+                }
+            }
+        })
+    }
+
+    /// Applies the `edit` to the input of the selected panel unless it is empty,
+    /// returning `true` if it was edited.
+    fn edit_input(&mut self, edit: impl FnOnce(&mut String)) -> bool {
+        if self.state.show_help {
+            return false;
+        }
+
+        let input = match self.state.selected_panel.kind() {
+            PanelKind::Letters => &mut self.state.input_letters,
+            PanelKind::Regex => &mut self.state.input_regex,
+            PanelKind::Length => &mut self.state.input_length,
+            PanelKind::Words => return false,
+        };
+        if input.is_empty() {
+            return false;
+        }
+
+        edit(input);
+        self.update_regex_error();
+        true
+    }
+
+    /// Compiles the regex input to keep the reason it is invalid, if it is.
+    fn update_regex_error(&mut self) {
+        let expr = &self.state.input_regex;
+        self.state.regex_error = if expr.is_empty() {
+            None
+        } else {
+            Regex::new(expr).err().map(|err| err.to_string())
+        };
+        self.state.regex_match_count = None;
+        self.state.regex_count_pending = !expr.is_empty() && self.state.regex_error.is_none();
+    }
+
     pub fn get_input_letters(&self) -> &str {
         &self.state.input_letters
     }
@@ -44,43 +188,574 @@ impl AppManager {
         &self.state.input_regex
     }
 
-    pub fn get_ouput_words(&self) -> &Vec<String> {
+    pub fn get_input_length(&self) -> &str {
+        &self.state.input_length
+    }
+
+    /// Returns the minimum and maximum word length entered in the Length panel or
+    /// `None` if the input can't be parsed.
+    ///
+    /// The input is either a single length like `5`, or a range like `3-7`, `3-`, or
+    /// `-7` where a missing bound is unlimited.
+    pub fn get_length_bounds(&self) -> Option<(Option<usize>, Option<usize>)> {
+        parse_length_bounds(&self.state.input_length)
+    }
+
+    pub fn is_length_valid(&self) -> bool {
+        self.get_length_bounds().is_some()
+    }
+
+    pub fn get_ouput_words(&self) -> &[(String, u32)] {
         &self.state.output_words
     }
 
-    pub fn set_output_words(&mut self, words: Vec<String>) {
+    /// Replaces the words and scores in the Words panel, keeping the selected word index
+    /// within the new words.
+    pub fn set_output_words(&mut self, words: Vec<(String, u32)>) {
+        self.state.selected_word = self.state.selected_word.min(words.len().saturating_sub(1));
         self.state.output_words = words;
     }
 
+    /// Returns the index of the selected word in the Words panel.
+    pub fn get_selected_word_idx(&self) -> usize {
+        self.state.selected_word
+    }
+
+    /// Returns the selected word in the Words panel without its score.
+    pub fn get_selected_word(&self) -> Option<&str> {
+        let (word, _score) = self.state.output_words.get(self.state.selected_word)?;
+        Some(word)
+    }
+
+    /// Moves the word selection up or down within the Words panel and returns `true` if
+    /// the selection moved.
+    ///
+    /// Returns `false` if the selection is already at the first or last word, or if the
+    /// `direction` is neither [`Direction::Up`] nor [`Direction::Down`].
+    pub fn move_word_selection(&mut self, direction: Direction) -> bool {
+        let selected = &mut self.state.selected_word;
+
+        match direction {
+            Direction::Up if *selected > 0 => *selected -= 1,
+            Direction::Down if *selected + 1 < self.state.output_words.len() => *selected += 1,
+            _ => return false,
+        }
+
+        true
+    }
+
+    /// Returns how long the search that produced the current words took, if one has
+    /// completed yet.
+    pub fn get_query_elapsed(&self) -> Option<Duration> {
+        self.state.query_elapsed
+    }
+
+    pub fn set_query_elapsed(&mut self, elapsed: Duration) {
+        self.state.query_elapsed = Some(elapsed);
+    }
+
+    /// Returns `true` while the current words are only those found so far by a search
+    /// that is still running.
+    pub fn is_search_partial(&self) -> bool {
+        self.state.search_partial
+    }
+
+    pub fn set_search_partial(&mut self, partial: bool) {
+        self.state.search_partial = partial;
+    }
+
+    /// Returns `true` from when a search is sent until its last words arrive.
+    pub fn is_searching(&self) -> bool {
+        self.state.searching
+    }
+
+    pub fn set_searching(&mut self, searching: bool) {
+        self.state.searching = searching;
+    }
+
+    /// Animates the spinner while searching, or rewinds it once the search is over.
+    pub fn tick_spinner(&mut self, now: Instant) {
+        if self.state.searching {
+            self.state.spinner.tick(now);
+        } else {
+            self.state.spinner.reset();
+        }
+    }
+
+    /// Returns the current frame of the spinner while searching.
+    pub fn get_spinner_frame(&self) -> Option<char> {
+        self.state.searching.then(|| self.state.spinner.frame())
+    }
+
+    /// Returns `true` if there are letters or a regex to search for.
+    pub fn has_query(&self) -> bool {
+        !(self.state.input_letters.is_empty() && self.state.input_regex.is_empty())
+    }
+
     pub fn is_regex_valid(&self) -> bool {
-        let expr = &self.state.input_regex;
-        expr.is_empty() || Regex::new(expr).is_ok()
+        self.state.regex_error.is_none()
+    }
+
+    /// Returns the reason the regex input is invalid, if it is.
+    ///
+    /// Syntax errors span several lines, pointing at the position of the error in the
+    /// expression.
+    pub fn get_regex_error(&self) -> Option<&str> {
+        self.state.regex_error.as_deref()
+    }
+
+    /// Returns `true` if the help overlay listing the keybindings is shown.
+    pub fn is_help_shown(&self) -> bool {
+        self.state.show_help
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.state.show_help = !self.state.show_help;
+    }
+
+    /// Returns `true` while asking to confirm quitting.
+    pub fn is_confirming_quit(&self) -> bool {
+        self.state.confirm_quit
+    }
+
+    pub fn set_confirm_quit(&mut self, confirm_quit: bool) {
+        self.state.confirm_quit = confirm_quit;
     }
+
+    pub fn get_sort_mode(&self) -> SortMode {
+        self.state.sort_mode
+    }
+
+    /// Switches between sorting the words by score and alphabetically.
+    pub fn toggle_sort_mode(&mut self) {
+        self.state.sort_mode = match self.state.sort_mode {
+            SortMode::Score => SortMode::Alpha,
+            SortMode::Alpha => SortMode::Score,
+        };
+    }
+
+    pub fn get_input_mode(&self) -> InputMode {
+        self.state.input_mode
+    }
+
+    pub fn set_input_mode(&mut self, input_mode: InputMode) {
+        self.state.input_mode = input_mode;
+    }
+
+    pub fn get_words_layout(&self) -> WordsLayout {
+        self.state.words_layout
+    }
+
+    /// Switches between joining the words and listing one per line.
+    pub fn toggle_words_layout(&mut self) {
+        self.state.words_layout = match self.state.words_layout {
+            WordsLayout::Joined => WordsLayout::OnePerLine,
+            WordsLayout::OnePerLine => WordsLayout::Joined,
+        };
+    }
+
+    /// Queues the query until the worker can take it, replacing the queued query of the
+    /// same kind that wasn't sent yet.
+    pub fn queue_query(&mut self, query: QueryRequest) {
+        if query.count_only {
+            self.state.queued_count = Some(query);
+        } else {
+            self.state.queued_query = Some(query);
+        }
+    }
+
+    /// Takes the queued query, or the queued count-only query, if there is one.
+    pub fn take_queued_query(&mut self, count_only: bool) -> Option<QueryRequest> {
+        if count_only {
+            self.state.queued_count.take()
+        } else {
+            self.state.queued_query.take()
+        }
+    }
+
+    /// Cancels the search replaced by the query that was just sent with the `cancelled`
+    /// flag.
+    ///
+    /// The replaced search is only cancelled once the worker took the new query so
+    /// that there is always a search of the latest inputs.
+    pub fn query_sent(&mut self, cancelled: Arc<AtomicBool>, count_only: bool) {
+        let prev = if count_only {
+            &mut self.state.regex_count_cancelled
+        } else {
+            &mut self.state.query_cancelled
+        };
+        std::mem::replace(prev, cancelled).store(true, Ordering::Relaxed);
+    }
+
+    /// Cancels the running searches so the worker can stop right away, like when
+    /// quitting.
+    pub fn cancel_searches(&self) {
+        self.state.query_cancelled.store(true, Ordering::Relaxed);
+        self.state
+            .regex_count_cancelled
+            .store(true, Ordering::Relaxed);
+    }
+
+    /// Returns the number of dictionary words matching the regex, once counted.
+    pub fn get_regex_match_count(&self) -> Option<usize> {
+        self.state.regex_match_count
+    }
+
+    pub fn set_regex_match_count(&mut self, count: usize) {
+        self.state.regex_match_count = Some(count);
+    }
+
+    /// Returns `true` if the regex changed since the words matching it were last
+    /// counted, then forgets about it until it changes again.
+    pub fn take_regex_count_pending(&mut self) -> bool {
+        std::mem::take(&mut self.state.regex_count_pending)
+    }
+
+    pub fn key_bindings(&self) -> &KeyBindings {
+        &self.state.key_bindings
+    }
+
+    pub fn set_key_bindings(&mut self, key_bindings: KeyBindings) {
+        self.state.key_bindings = key_bindings;
+    }
+
+    /// Returns how the words and scores are written in the Words panel.
+    pub fn result_format(&self) -> &ResultFormat {
+        &self.state.result_format
+    }
+
+    pub fn set_result_format(&mut self, result_format: ResultFormat) {
+        self.state.result_format = result_format;
+    }
+
+    pub fn get_theme(&self) -> Theme {
+        self.state.theme
+    }
+
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.state.theme = theme;
+    }
+
+    /// Returns `true` if panel states should be conveyed without relying on color.
+    pub fn is_no_color(&self) -> bool {
+        self.state.no_color
+    }
+
+    pub fn set_no_color(&mut self, no_color: bool) {
+        self.state.no_color = no_color;
+    }
+}
+
+/// How the words in the Words panel are sorted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SortMode {
+    /// Highest score first
+    #[default]
+    Score,
+    /// Alphabetically
+    Alpha,
+}
+
+/// Whether the typed keys go into the inputs or navigate between panels.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InputMode {
+    /// Keys navigate, like `hjkl` to select the panels
+    Normal,
+    /// Keys are typed into the selected input
+    #[default]
+    Insert,
+}
+
+/// How the words are laid out in the Words panel.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WordsLayout {
+    /// Separated by commas and wrapped
+    #[default]
+    Joined,
+    /// Each word on its own line
+    OnePerLine,
+}
+
+/// Parses the bounds entered in the Length panel. See [`AppManager::get_length_bounds`].
+fn parse_length_bounds(input: &str) -> Option<(Option<usize>, Option<usize>)> {
+    fn parse_bound(bound: &str) -> Option<Option<usize>> {
+        if bound.is_empty() {
+            return Some(None);
+        }
+        bound.parse().ok().map(Some)
+    }
+
+    let (min, max) = match input.split_once('-') {
+        Some((min, max)) => (parse_bound(min)?, parse_bound(max)?),
+        None => {
+            let len = parse_bound(input)?;
+            (len, len)
+        }
+    };
+
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return None;
+        }
+    }
+
+    Some((min, max))
 }
 
 pub struct AppState {
     input_letters: String,
     input_regex: String,
-    output_words: Vec<String>,
+    regex_error: Option<String>,
+    input_length: String,
+    output_words: Vec<(String, u32)>,
+    selected_word: usize,
+    query_elapsed: Option<Duration>,
+    search_partial: bool,
+    searching: bool,
+    spinner: Spinner,
+    input_mode: InputMode,
+    sort_mode: SortMode,
+    words_layout: WordsLayout,
+    show_help: bool,
+    confirm_quit: bool,
+    history: History,
+    key_bindings: KeyBindings,
+    result_format: ResultFormat,
+    query_cancelled: Arc<AtomicBool>,
+    /// The query the worker couldn't take yet, to be sent again.
+    queued_query: Option<QueryRequest>,
+    queued_count: Option<QueryRequest>,
+    regex_match_count: Option<usize>,
+    regex_count_pending: bool,
+    regex_count_cancelled: Arc<AtomicBool>,
     selected_panel: PanelRef,
+    theme: Theme,
+    no_color: bool,
 }
 
 impl Default for AppState {
     fn default() -> Self {
         let letters = PanelRef::new(PanelKind::Letters);
         let regex = PanelRef::new(PanelKind::Regex);
+        let length = PanelRef::new(PanelKind::Length);
         let words = PanelRef::new(PanelKind::Words);
 
         letters.link(Direction::Right, regex.clone());
         letters.link(Direction::Down, words.clone());
         regex.link(Direction::Left, letters.clone());
+        regex.link(Direction::Right, length.clone());
         regex.link(Direction::Down, words.clone());
+        length.link(Direction::Left, regex.clone());
+        length.link(Direction::Down, words.clone());
 
         Self {
             selected_panel: letters,
             input_letters: String::new(),
             input_regex: String::new(),
+            regex_error: None,
+            input_length: String::new(),
             output_words: Vec::new(),
+            selected_word: 0,
+            query_elapsed: None,
+            search_partial: false,
+            searching: false,
+            spinner: Spinner::default(),
+            input_mode: InputMode::default(),
+            sort_mode: SortMode::default(),
+            words_layout: WordsLayout::default(),
+            show_help: false,
+            confirm_quit: false,
+            history: History::default(),
+            key_bindings: KeyBindings::default(),
+            result_format: ResultFormat::default(),
+            query_cancelled: Arc::default(),
+            queued_query: None,
+            queued_count: None,
+            regex_match_count: None,
+            regex_count_pending: false,
+            regex_count_cancelled: Arc::default(),
+            theme: Theme::default(),
+            no_color: false,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_length_bounds() {
+        assert_eq!(parse_length_bounds(""), Some((None, None)));
+        assert_eq!(parse_length_bounds("5"), Some((Some(5), Some(5))));
+        assert_eq!(parse_length_bounds("3-7"), Some((Some(3), Some(7))));
+        assert_eq!(parse_length_bounds("3-"), Some((Some(3), None)));
+        assert_eq!(parse_length_bounds("-7"), Some((None, Some(7))));
+        assert_eq!(parse_length_bounds("7-3"), None);
+        assert_eq!(parse_length_bounds("a"), None);
+        assert_eq!(parse_length_bounds("1-2-3"), None);
+    }
+
+    #[test]
+    fn test_move_word_selection() {
+        let mut mngr = AppManager::default();
+
+        assert_eq!(mngr.get_selected_word(), None);
+        assert!(!mngr.move_word_selection(Direction::Down));
+
+        let words = [("radar", 6), ("cart", 6), ("dart", 5)];
+        mngr.set_output_words(words.map(|(w, s)| (w.to_string(), s)).to_vec());
+
+        assert_eq!(mngr.get_selected_word(), Some("radar"));
+        assert!(!mngr.move_word_selection(Direction::Up));
+
+        assert!(mngr.move_word_selection(Direction::Down));
+        assert!(mngr.move_word_selection(Direction::Down));
+        assert!(!mngr.move_word_selection(Direction::Down));
+        assert_eq!(mngr.get_selected_word(), Some("dart"));
+
+        assert!(mngr.move_word_selection(Direction::Up));
+        assert_eq!(mngr.get_selected_word(), Some("cart"));
+        assert!(!mngr.move_word_selection(Direction::Left));
+    }
+
+    #[test]
+    fn test_word_selection_is_clamped_to_new_words() {
+        let mut mngr = AppManager::default();
+        let words = [("radar", 6), ("cart", 6), ("dart", 5)];
+        mngr.set_output_words(words.map(|(w, s)| (w.to_string(), s)).to_vec());
+        mngr.move_word_selection(Direction::Down);
+        mngr.move_word_selection(Direction::Down);
+
+        mngr.set_output_words(vec![("car".to_string(), 5), ("rad".to_string(), 4)]);
+        assert_eq!(mngr.get_selected_word_idx(), 1);
+        assert_eq!(mngr.get_selected_word(), Some("rad"));
+
+        mngr.set_output_words(Vec::new());
+        assert_eq!(mngr.get_selected_word_idx(), 0);
+        assert_eq!(mngr.get_selected_word(), None);
+    }
+
+    #[test]
+    fn test_regex_error() {
+        let mut mngr = AppManager::default();
+        mngr.select_panel(Direction::Right);
+
+        "[a-".chars().for_each(|ch| {
+            mngr.push_ch(ch);
+        });
+
+        assert!(!mngr.is_regex_valid());
+        assert!(mngr.get_regex_error().is_some_and(|err| !err.is_empty()));
+
+        mngr.pop_ch();
+        mngr.push_ch('z');
+        mngr.push_ch(']');
+
+        assert!(mngr.is_regex_valid());
+        assert_eq!(mngr.get_regex_error(), None);
+    }
+
+    #[test]
+    fn test_help_suppresses_edits() {
+        let mut mngr = AppManager::default();
+        mngr.push_ch('a');
+
+        mngr.toggle_help();
+        assert!(mngr.is_help_shown());
+
+        assert!(!mngr.push_ch('b'));
+        assert!(!mngr.pop_ch());
+        assert_eq!(mngr.get_input_letters(), "a");
+
+        mngr.toggle_help();
+        assert!(!mngr.is_help_shown());
+
+        assert!(mngr.push_ch('b'));
+        assert_eq!(mngr.get_input_letters(), "ab");
+    }
+
+    #[test]
+    fn test_clear_input() {
+        let mut mngr = AppManager::default();
+        mngr.push_ch('a');
+        mngr.select_panel(Direction::Right);
+        mngr.push_ch('(');
+
+        assert!(mngr.clear_input());
+        assert_eq!(mngr.get_input_regex(), "");
+        assert_eq!(mngr.get_regex_error(), None);
+        assert!(!mngr.clear_input());
+        assert_eq!(mngr.get_input_letters(), "a");
+    }
+
+    #[test]
+    fn test_delete_word() {
+        let mut mngr = AppManager::default();
+        mngr.select_panel(Direction::Right);
+        "^ca(rt".chars().for_each(|ch| {
+            mngr.push_ch(ch);
+        });
+
+        assert!(mngr.delete_word());
+        assert_eq!(mngr.get_input_regex(), "^ca(");
+        assert!(mngr.delete_word());
+        assert_eq!(mngr.get_input_regex(), "^ca");
+        assert!(mngr.delete_word());
+        assert!(mngr.delete_word());
+        assert_eq!(mngr.get_input_regex(), "");
+        assert!(!mngr.delete_word());
+    }
+
+    #[test]
+    fn test_clear_all() {
+        let mut mngr = AppManager::default();
+        mngr.push_ch('a');
+        mngr.select_panel(Direction::Right);
+        mngr.push_ch('(');
+        mngr.set_output_words(vec![("a".to_string(), 1)]);
+        mngr.set_query_elapsed(Duration::from_millis(3));
+
+        assert!(mngr.clear_all());
+        assert_eq!(mngr.get_input_letters(), "");
+        assert_eq!(mngr.get_input_regex(), "");
+        assert_eq!(mngr.get_regex_error(), None);
+        assert!(mngr.get_ouput_words().is_empty());
+        assert_eq!(mngr.get_query_elapsed(), None);
+        assert!(!mngr.clear_all());
+    }
+
+    #[test]
+    fn test_recall_history() {
+        let mut mngr = AppManager::default();
+        "cat".chars().for_each(|ch| {
+            mngr.push_ch(ch);
+        });
+        mngr.push_history();
+        mngr.clear_input();
+        mngr.push_ch('d');
+
+        assert!(mngr.recall_history(Direction::Up));
+        assert_eq!(mngr.get_input_letters(), "cat");
+        assert!(!mngr.recall_history(Direction::Up));
+        assert!(mngr.recall_history(Direction::Down));
+        assert_eq!(mngr.get_input_letters(), "d");
+
+        mngr.select_panel(Direction::Right);
+        assert!(!mngr.recall_history(Direction::Up));
+    }
+
+    #[test]
+    fn test_toggle_sort_mode() {
+        let mut mngr = AppManager::default();
+
+        assert_eq!(mngr.get_sort_mode(), SortMode::Score);
+
+        mngr.toggle_sort_mode();
+        assert_eq!(mngr.get_sort_mode(), SortMode::Alpha);
+
+        mngr.toggle_sort_mode();
+        assert_eq!(mngr.get_sort_mode(), SortMode::Score);
+    }
+}
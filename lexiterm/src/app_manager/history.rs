@@ -0,0 +1,101 @@
+use std::collections::VecDeque;
+
+/// The previous racks, oldest first, that can be recalled into the Letters panel.
+#[derive(Debug, Default)]
+pub struct History {
+    entries: VecDeque<String>,
+    /// The index of the recalled entry, if one is being recalled.
+    cursor: Option<usize>,
+    /// The rack that was being typed before recalling, restored after the newest entry.
+    draft: String,
+}
+
+impl History {
+    /// The number of racks kept, older ones are forgotten first.
+    const CAPACITY: usize = 100;
+
+    /// Adds the rack as the newest entry, unless it is empty or the same as the newest
+    /// entry, and stops recalling.
+    pub fn push(&mut self, rack: &str) {
+        self.cursor = None;
+        if rack.is_empty() || self.entries.back().is_some_and(|last| last == rack) {
+            return;
+        }
+
+        if self.entries.len() == Self::CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(rack.to_string());
+    }
+
+    /// Recalls the entry before the one being recalled, keeping the `current` rack as
+    /// the draft when starting to recall. Returns `None` if there is no older entry.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let cursor = match self.cursor {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => return None,
+            Some(cursor) => cursor - 1,
+        };
+
+        self.cursor = Some(cursor);
+        Some(&self.entries[cursor])
+    }
+
+    /// Recalls the entry after the one being recalled, or the draft after the newest
+    /// entry. Returns `None` if nothing is being recalled.
+    pub fn newer(&mut self) -> Option<&str> {
+        let cursor = self.cursor? + 1;
+        if cursor < self.entries.len() {
+            self.cursor = Some(cursor);
+            Some(&self.entries[cursor])
+        } else {
+            self.cursor = None;
+            Some(&self.draft)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_push_skips_consecutive_duplicates() {
+        let mut history = History::default();
+        ["radar", "radar", "", "cart", "radar"]
+            .iter()
+            .for_each(|rack| history.push(rack));
+
+        assert_eq!(history.entries, ["radar", "cart", "radar"]);
+    }
+
+    #[test]
+    fn test_push_forgets_the_oldest_rack() {
+        let mut history = History::default();
+        (0..=History::CAPACITY).for_each(|i| history.push(&i.to_string()));
+
+        assert_eq!(history.entries.len(), History::CAPACITY);
+        assert_eq!(history.entries.front().map(String::as_str), Some("1"));
+    }
+
+    #[test]
+    fn test_recall() {
+        let mut history = History::default();
+        assert_eq!(history.older("ca"), None);
+
+        history.push("radar");
+        history.push("cart");
+
+        assert_eq!(history.older("ca"), Some("cart"));
+        assert_eq!(history.older("cart"), Some("radar"));
+        assert_eq!(history.older("radar"), None);
+        assert_eq!(history.newer(), Some("cart"));
+        assert_eq!(history.newer(), Some("ca"));
+        assert_eq!(history.newer(), None);
+    }
+}
@@ -24,6 +24,51 @@ impl AppManager {
         true
     }
 
+    /// Selects the [`Panel`] of the given [`PanelKind`] and return `true` if a new panel
+    /// was selected.
+    pub fn select_panel_kind(&mut self, kind: PanelKind) -> bool {
+        let selected = self.selected_panel();
+        if selected.kind() == kind {
+            return false;
+        }
+
+        // Search the panels reachable from the selected one
+        let mut visited = vec![selected.kind()];
+        let mut to_visit = vec![selected];
+        let mut new_selected = None;
+
+        while let Some(panel) = to_visit.pop() {
+            for linked in panel.0.borrow().links.values() {
+                if linked.kind() == kind {
+                    new_selected = Some(linked.clone());
+                } else if !visited.contains(&linked.kind()) {
+                    visited.push(linked.kind());
+                    to_visit.push(linked.clone());
+                }
+            }
+
+            if new_selected.is_some() {
+                break;
+            }
+        }
+
+        let Some(new_selected) = new_selected else {
+            return false;
+        };
+
+        if kind == PanelKind::Words {
+            new_selected
+                .0
+                .borrow_mut()
+                .links
+                .insert(Direction::Up, self.selected_panel());
+        }
+
+        self.state.selected_panel = new_selected;
+
+        true
+    }
+
     /// Returns the [`PanelKind`] of the currently selected [`Panel`].
     pub fn selected_panel(&self) -> PanelRef {
         self.state.selected_panel.clone()
@@ -79,6 +124,7 @@ impl PanelRef {
 pub enum PanelKind {
     Letters,
     Regex,
+    Length,
     Words,
 }
 
@@ -122,4 +168,49 @@ mod test {
 
         assert_eq!(mngr.selected_panel().kind(), PanelKind::Words);
     }
+
+    #[test]
+    fn test_select_panel_kind() {
+        let mut mngr = AppManager::default();
+
+        assert!(mngr.select_panel_kind(PanelKind::Length));
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Length);
+
+        assert!(!mngr.select_panel_kind(PanelKind::Length));
+
+        assert!(mngr.select_panel_kind(PanelKind::Words));
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Words);
+
+        // going back up returns to the panel the Words panel was selected from
+        mngr.select_panel(Direction::Up);
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Length);
+
+        assert!(mngr.select_panel_kind(PanelKind::Letters));
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Letters);
+    }
+
+    #[test]
+    fn test_select_length_panel() {
+        let mut mngr = AppManager::default();
+
+        mngr.select_panel(Direction::Right);
+        mngr.select_panel(Direction::Right);
+
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Length);
+
+        assert!(!mngr.select_panel(Direction::Right));
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Length);
+
+        mngr.select_panel(Direction::Down);
+
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Words);
+
+        mngr.select_panel(Direction::Up);
+
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Length);
+
+        mngr.select_panel(Direction::Left);
+
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Regex);
+    }
 }
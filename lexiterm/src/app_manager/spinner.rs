@@ -0,0 +1,67 @@
+use std::time::{Duration, Instant};
+
+/// An animation shown while a search is running so the UI doesn't look frozen.
+#[derive(Debug, Default)]
+pub struct Spinner {
+    frame: usize,
+    /// When the current frame was first shown.
+    shown_at: Option<Instant>,
+}
+
+impl Spinner {
+    const FRAMES: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+    /// How long each frame is shown.
+    const FRAME_DUR: Duration = Duration::from_millis(80);
+
+    /// Moves to the next frame once the current one was shown long enough.
+    pub fn tick(&mut self, now: Instant) {
+        let Some(shown_at) = self.shown_at else {
+            self.shown_at = Some(now);
+            return;
+        };
+
+        if now.duration_since(shown_at) >= Self::FRAME_DUR {
+            self.frame = (self.frame + 1) % Self::FRAMES.len();
+            self.shown_at = Some(now);
+        }
+    }
+
+    /// Starts over from the first frame.
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    pub fn frame(&self) -> char {
+        Self::FRAMES[self.frame]
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_tick() {
+        let mut spinner = Spinner::default();
+        let start = Instant::now();
+
+        spinner.tick(start);
+        assert_eq!(spinner.frame(), '⠋');
+
+        spinner.tick(start + Spinner::FRAME_DUR / 2);
+        assert_eq!(spinner.frame(), '⠋');
+
+        spinner.tick(start + Spinner::FRAME_DUR);
+        assert_eq!(spinner.frame(), '⠙');
+
+        (0..Spinner::FRAMES.len()).for_each(|i| {
+            spinner.tick(start + Spinner::FRAME_DUR * (i as u32 + 2));
+        });
+        assert_eq!(spinner.frame(), '⠙');
+
+        spinner.reset();
+        assert_eq!(spinner.frame(), '⠋');
+    }
+}
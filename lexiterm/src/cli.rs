@@ -0,0 +1,140 @@
+//! Parses the command line arguments.
+
+use clap::{Parser, ValueEnum};
+use std::env;
+use std::ffi::OsStr;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(version, about)]
+pub struct Args {
+    /// Path to the words file, one word per line
+    #[arg(long, value_name = "PATH", default_value = "./words.txt")]
+    pub words: PathBuf,
+
+    /// Path to the scores file, one `letter=score` per line
+    #[arg(long, value_name = "PATH", default_value = "./char_scores.txt")]
+    pub scores: PathBuf,
+
+    /// Path to the config file, `~/.config/lexiterm/config.toml` on Linux by default
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Mark the selected and invalid panels with text instead of color, also enabled by
+    /// setting the `NO_COLOR` environment variable
+    #[arg(long)]
+    pub no_color: bool,
+
+    /// Print the words that could be built using these letters and exit without
+    /// starting the TUI
+    #[arg(long, value_name = "LETTERS")]
+    pub query: Option<String>,
+
+    /// Only print the words matching this regular expression
+    #[arg(long, value_name = "REGEX", requires = "query")]
+    pub regex: Option<String>,
+
+    /// Print at most this many words
+    #[arg(long, value_name = "N", requires = "query")]
+    pub limit: Option<usize>,
+
+    /// How to sort the printed words
+    #[arg(long, value_enum, default_value_t = SortOrder::Score, requires = "query")]
+    pub sort: SortOrder,
+
+    /// How to print the words
+    #[arg(long, value_enum, default_value_t = OutputFormat::Plain, requires = "query")]
+    pub format: OutputFormat,
+}
+
+impl Args {
+    /// Returns `true` if colors are turned off by `--no-color` or the `NO_COLOR`
+    /// environment variable.
+    pub fn is_no_color(&self) -> bool {
+        is_no_color(self.no_color, env::var_os("NO_COLOR").as_deref())
+    }
+}
+
+/// Colors are turned off by the flag or by a non-empty `NO_COLOR`, see
+/// <https://no-color.org>.
+fn is_no_color(flag: bool, no_color_env: Option<&OsStr>) -> bool {
+    flag || no_color_env.is_some_and(|value| !value.is_empty())
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortOrder {
+    /// Highest score first
+    #[default]
+    Score,
+    /// Alphabetically
+    Alpha,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One result per line, written using the `result_format` of the config
+    #[default]
+    Plain,
+    /// A JSON array of `{"word": ..., "score": ...}` objects
+    Json,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_default_paths() {
+        let args = Args::parse_from(["lexiterm"]);
+
+        assert_eq!(args.words, PathBuf::from("./words.txt"));
+        assert_eq!(args.scores, PathBuf::from("./char_scores.txt"));
+        assert_eq!(args.config, None);
+        assert!(!args.no_color);
+    }
+
+    #[test]
+    fn test_custom_paths() {
+        let args = Args::parse_from([
+            "lexiterm",
+            "--words",
+            "/usr/share/dict/words",
+            "--scores",
+            "scores.txt",
+            "--config",
+            "config.toml",
+            "--no-color",
+        ]);
+
+        assert_eq!(args.words, PathBuf::from("/usr/share/dict/words"));
+        assert_eq!(args.scores, PathBuf::from("scores.txt"));
+        assert_eq!(args.config, Some(PathBuf::from("config.toml")));
+        assert!(args.no_color);
+    }
+
+    #[test]
+    fn test_no_color_env() {
+        assert!(!is_no_color(false, None));
+        assert!(!is_no_color(false, Some(OsStr::new(""))));
+        assert!(is_no_color(false, Some(OsStr::new("1"))));
+        assert!(is_no_color(true, None));
+    }
+
+    #[test]
+    fn test_query_args() {
+        let args = Args::parse_from([
+            "lexiterm", "--query", "radar", "--regex", "^r", "--limit", "3", "--sort", "alpha",
+        ]);
+
+        assert_eq!(args.query.as_deref(), Some("radar"));
+        assert_eq!(args.regex.as_deref(), Some("^r"));
+        assert_eq!(args.limit, Some(3));
+        assert_eq!(args.sort, SortOrder::Alpha);
+    }
+
+    #[test]
+    fn test_regex_requires_query() {
+        assert!(Args::try_parse_from(["lexiterm", "--regex", "^r"]).is_err());
+    }
+}
@@ -0,0 +1,405 @@
+//! Loads the user configuration from a TOML file.
+//!
+//! Every setting is optional so the file only needs to list what differs from the
+//! defaults, e.g.:
+//!
+//! ```toml
+//! result_format = "{word} ({score})"
+//! theme = "high-contrast"
+//!
+//! [keybindings]
+//! exit = "F10"
+//! up = "Tab"
+//! ```
+
+use anyhow::{anyhow, Context, Result};
+use crossterm::event::KeyCode;
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Config {
+    pub result_format: ResultFormat,
+    pub theme: Theme,
+    pub keybindings: KeyBindings,
+    pub channels: Channels,
+}
+
+impl Config {
+    /// Returns the path of the config file, e.g. `~/.config/lexiterm/config.toml` on
+    /// Linux.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("lexiterm").join("config.toml"))
+    }
+
+    /// Loads the config from the file or returns the default config if the file doesn't
+    /// exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => {
+                return Err(err).with_context(|| format!("failed to read `{}`", path.display()))
+            }
+        };
+
+        toml::from_str(&content).with_context(|| format!("invalid config `{}`", path.display()))
+    }
+}
+
+/// How a result is written in the Words panel and the one-shot output, where `{word}`
+/// and `{score}` are replaced by the word and its score, e.g. `"{word} ({score})"`.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct ResultFormat(String);
+
+impl Default for ResultFormat {
+    fn default() -> Self {
+        Self("{word}:{score}".to_string())
+    }
+}
+
+impl TryFrom<String> for ResultFormat {
+    type Error = anyhow::Error;
+
+    fn try_from(template: String) -> Result<Self> {
+        if !template.contains(ResultFormat::WORD) {
+            return Err(anyhow!("result format `{template}` is missing `{{word}}`"));
+        }
+        Ok(Self(template))
+    }
+}
+
+impl ResultFormat {
+    const WORD: &str = "{word}";
+    const SCORE: &str = "{score}";
+
+    /// Writes the word and its score using the template.
+    pub fn format(&self, word: &str, score: u32) -> String {
+        self.segments()
+            .map(|segment| match segment {
+                Segment::Text(text) => text.to_string(),
+                Segment::Word => word.to_string(),
+                Segment::Score => score.to_string(),
+            })
+            .collect()
+    }
+
+    /// Splits the template into its text and placeholders, in order.
+    pub fn segments(&self) -> impl Iterator<Item = Segment<'_>> {
+        let mut rest = self.0.as_str();
+        std::iter::from_fn(move || {
+            if rest.is_empty() {
+                return None;
+            }
+            for (placeholder, segment) in
+                [(Self::WORD, Segment::Word), (Self::SCORE, Segment::Score)]
+            {
+                if let Some(after) = rest.strip_prefix(placeholder) {
+                    rest = after;
+                    return Some(segment);
+                }
+            }
+
+            // The text runs until the next placeholder
+            let end = [Self::WORD, Self::SCORE]
+                .iter()
+                .filter_map(|placeholder| rest.find(placeholder))
+                .min()
+                .unwrap_or(rest.len());
+            let (text, after) = rest.split_at(end);
+            rest = after;
+            Some(Segment::Text(text))
+        })
+    }
+}
+
+/// A part of a [`ResultFormat`] template.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    Text(&'a str),
+    Word,
+    Score,
+}
+
+/// The colors of the panel borders, either a built-in theme like `"high-contrast"` or
+/// a table of colors like `{ selected = "cyan", error = "#ff5555" }`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "ThemeSpec")]
+pub struct Theme {
+    /// The border color of the selected panel.
+    pub selected: Color,
+    /// The border color of panels with an invalid input and of the errors.
+    pub error: Color,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+impl Theme {
+    pub const DEFAULT: Self = Self {
+        selected: Color::Yellow,
+        error: Color::Red,
+    };
+
+    pub const HIGH_CONTRAST: Self = Self {
+        selected: Color::White,
+        error: Color::LightRed,
+    };
+
+    /// Returns the built-in theme with the name, if any.
+    pub fn named(name: &str) -> Option<Self> {
+        match name {
+            "default" => Some(Self::DEFAULT),
+            "high-contrast" => Some(Self::HIGH_CONTRAST),
+            _ => None,
+        }
+    }
+}
+
+/// How a [`Theme`] is written in the config file.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ThemeSpec {
+    Named(String),
+    Colors {
+        selected: Option<String>,
+        error: Option<String>,
+    },
+}
+
+impl TryFrom<ThemeSpec> for Theme {
+    type Error = anyhow::Error;
+
+    fn try_from(spec: ThemeSpec) -> Result<Self> {
+        let parse_color = |color: Option<String>, default| match color {
+            Some(color) => color
+                .parse()
+                .map_err(|_| anyhow!("unknown color `{color}`")),
+            None => Ok(default),
+        };
+
+        match spec {
+            ThemeSpec::Named(name) => {
+                Self::named(&name).ok_or_else(|| anyhow!("unknown theme `{name}`"))
+            }
+            ThemeSpec::Colors { selected, error } => Ok(Self {
+                selected: parse_color(selected, Self::DEFAULT.selected)?,
+                error: parse_color(error, Self::DEFAULT.error)?,
+            }),
+        }
+    }
+}
+
+/// The capacities of the channels between the TUI and the search worker.
+///
+/// A capacity of 0 makes a rendezvous channel, so no outdated queries or results can
+/// pile up. A query is only handed off while the worker is waiting for one. The query
+/// typed during a search is kept by the TUI and sent once the search is over, since a
+/// search is only cancelled after the worker took the query replacing it. Likewise,
+/// the worker waits after each search until the TUI reads its result, and most partial
+/// results are dropped since the TUI only reads between frames. Larger capacities let
+/// both sides carry on without waiting, at the cost of queuing responses that are
+/// outdated by the time they are read.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Channels {
+    /// How many queries can wait for the worker.
+    pub queries: usize,
+    /// How many responses can wait for the TUI.
+    pub results: usize,
+}
+
+impl Default for Channels {
+    fn default() -> Self {
+        Self {
+            queries: 100,
+            results: 30,
+        }
+    }
+}
+
+/// The keys that trigger each action.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct KeyBindings {
+    pub exit: Key,
+    pub up: Key,
+    pub down: Key,
+    pub left: Key,
+    pub right: Key,
+    pub backspace: Key,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self {
+            exit: Key(KeyCode::Esc),
+            up: Key(KeyCode::Up),
+            down: Key(KeyCode::Down),
+            left: Key(KeyCode::Left),
+            right: Key(KeyCode::Right),
+            backspace: Key(KeyCode::Backspace),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// Returns the [`Action`] bound to the key, if any.
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        [
+            (self.exit, Action::Exit),
+            (self.up, Action::Up),
+            (self.down, Action::Down),
+            (self.left, Action::Left),
+            (self.right, Action::Right),
+            (self.backspace, Action::Backspace),
+        ]
+        .into_iter()
+        .find_map(|(key, action)| (key.0 == code).then_some(action))
+    }
+}
+
+/// The actions that can be bound to a key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Exit,
+    Up,
+    Down,
+    Left,
+    Right,
+    Backspace,
+}
+
+/// A key written either as a single character like `"q"` or by its name like `"Esc"`,
+/// `"Tab"`, or `"F1"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct Key(pub KeyCode);
+
+impl TryFrom<String> for Key {
+    type Error = anyhow::Error;
+
+    fn try_from(name: String) -> Result<Self> {
+        let mut chars = name.chars();
+        if let (Some(ch), None) = (chars.next(), chars.next()) {
+            return Ok(Self(KeyCode::Char(ch)));
+        }
+
+        let code = match name.to_lowercase().as_str() {
+            "esc" => KeyCode::Esc,
+            "enter" => KeyCode::Enter,
+            "tab" => KeyCode::Tab,
+            "backspace" => KeyCode::Backspace,
+            "delete" => KeyCode::Delete,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            "space" => KeyCode::Char(' '),
+            other => other
+                .strip_prefix('f')
+                .and_then(|n| n.parse().ok())
+                .map(KeyCode::F)
+                .ok_or_else(|| anyhow!("unknown key `{name}`"))?,
+        };
+
+        Ok(Self(code))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_parse_keybindings() {
+        let config: Config = toml::from_str(
+            r#"
+            [keybindings]
+            exit = "q"
+            up = "F2"
+            "#,
+        )
+        .expect("valid config");
+
+        assert_eq!(
+            config.keybindings,
+            KeyBindings {
+                exit: Key(KeyCode::Char('q')),
+                up: Key(KeyCode::F(2)),
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn test_reject_unknown_key() {
+        assert!(toml::from_str::<Config>("[keybindings]\nexit = \"Escape!\"").is_err());
+    }
+
+    #[test]
+    fn test_result_formats() {
+        let plain = ResultFormat::default();
+        let config: Config =
+            toml::from_str(r#"result_format = "{score} - {word}""#).expect("valid config");
+        let scored = config.result_format;
+
+        assert_eq!(plain.format("radar", 6), "radar:6");
+        assert_eq!(scored.format("radar", 6), "6 - radar");
+    }
+
+    #[test]
+    fn test_reject_result_format_without_word() {
+        assert!(toml::from_str::<Config>(r#"result_format = "{score}""#).is_err());
+    }
+
+    #[test]
+    fn test_parse_theme() {
+        let config: Config = toml::from_str(r#"theme = "high-contrast""#).expect("valid config");
+        assert_eq!(config.theme, Theme::HIGH_CONTRAST);
+
+        let config: Config =
+            toml::from_str("[theme]\nselected = \"#00ffff\"").expect("valid config");
+        assert_eq!(
+            config.theme,
+            Theme {
+                selected: Color::Rgb(0, 255, 255),
+                ..Theme::DEFAULT
+            }
+        );
+
+        assert!(toml::from_str::<Config>(r#"theme = "neon""#).is_err());
+        assert!(toml::from_str::<Config>("[theme]\nerror = \"reddish\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_channels() {
+        let config: Config = toml::from_str("[channels]\nresults = 0").expect("valid config");
+
+        assert_eq!(
+            config.channels,
+            Channels {
+                queries: 100,
+                results: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_missing_config_falls_back_to_defaults() {
+        let config = Config::load(Path::new("./does/not/exist.toml")).expect("default config");
+
+        assert_eq!(config, Config::default());
+    }
+}
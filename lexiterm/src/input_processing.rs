@@ -4,22 +4,86 @@
 //! queries to the worker thread while handling responses.
 
 use crate::app_manager::*;
+use crate::config::{Action, KeyBindings};
 use crate::search_worker::{QueryRequest, QueryResponse};
 use crate::tui_renderer::*;
 use anyhow::{anyhow, Result};
-use crossbeam::channel::{Receiver, Sender, TrySendError};
-use crossterm::event::{self, Event, KeyCode};
+use arboard::Clipboard;
+use crossbeam::channel::{Receiver, Sender, TryRecvError, TrySendError};
+use crossterm::event::{
+    self, Event, KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use crossterm::terminal;
+use ratatui::layout::Rect;
+use std::cell::RefCell;
+use std::ops::ControlFlow;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 /// Represents different types of input events from the terminal.
 ///
 /// This enum categorizes key events into meaningful actions for the application.
+#[derive(Debug, PartialEq)]
 enum InputEvent {
     NoOp,
     Exit,
+    /// Asks to confirm quitting, unlike [`InputEvent::Exit`] which may only leave a mode
+    Quit,
     AppendCharToInputLetters(char),
     BackSpace,
+    /// Clears the selected input, like Ctrl-U in a shell
+    ClearInput,
+    /// Deletes the last word of the selected input, like Ctrl-W in a shell
+    DeleteWord,
+    /// Clears every input and the words found
+    ClearAll,
+    /// Recalls an older rack when going up or a newer one when going down
+    RecallHistory(Direction),
+    Enter,
+    ToggleHelp,
     SelectPanel(Direction),
+    Click {
+        column: u16,
+        row: u16,
+    },
+}
+
+/// Toggles the [`SortMode`] while the Words panel is selected.
+const TOGGLE_SORT_KEY: char = 's';
+
+/// Toggles the [`WordsLayout`] while the Words panel is selected.
+const TOGGLE_LAYOUT_KEY: char = 'w';
+
+/// Asks to confirm quitting while in [`InputMode::Normal`].
+const QUIT_KEY: char = 'q';
+
+/// Confirms quitting, any other key cancels it.
+const CONFIRM_QUIT_KEY: char = 'y';
+
+/// Switches from [`InputMode::Normal`] to [`InputMode::Insert`].
+const INSERT_KEY: char = 'i';
+
+/// Toggles the help overlay, except in the Regex panel where it is typed instead.
+const HELP_KEY: char = '?';
+
+/// Copies the selected word, like Enter, while the Words panel is selected.
+const COPY_KEY: char = 'y';
+
+thread_local! {
+    /// The system clipboard or `None` if there isn't one, like in a headless session.
+    ///
+    /// It is kept around since some platforms clear the copied text once the
+    /// [`Clipboard`] is dropped.
+    static CLIPBOARD: RefCell<Option<Clipboard>> = RefCell::new(Clipboard::new().ok());
+}
+
+/// Copies the text to the system clipboard, doing nothing if there is no clipboard.
+fn copy_to_clipboard(text: &str) {
+    CLIPBOARD.with_borrow_mut(|clipboard| {
+        if let Some(clipboard) = clipboard {
+            let _ = clipboard.set_text(text);
+        }
+    });
 }
 
 /// Listens for terminal input events and updates the UI accordingly.
@@ -27,13 +91,18 @@ enum InputEvent {
 /// This function continuously listens for key events, processes them, and sends search
 /// queries to the worker thread while updating the terminal UI with results.
 pub fn listen_and_process(
-    mut mngr: AppManager,
+    mngr: &mut AppManager,
     mut renderer: TuiRenderer,
     query_tx: &Sender<QueryRequest>,
     result_rx: &Receiver<QueryResponse>,
 ) -> Result<()> {
+    // Search for the inputs restored from the last session, if any
+    if mngr.has_query() {
+        mngr.send_query(query_tx)?;
+    }
+
     // handle input events
-    renderer.draw_frame(&mngr)?;
+    renderer.draw_frame(mngr)?;
 
     loop {
         if mngr.process_event(query_tx)? {
@@ -41,14 +110,46 @@ pub fn listen_and_process(
             break;
         }
 
+        // Retry the queries the worker was too busy to take
+        mngr.send_queued_queries(query_tx)?;
+
         // Check if the worker thread has responded
-        if let Some(resp) = result_rx.try_recv().into_iter().next_back() {
-            mngr.set_output_words(resp.words);
-        }
+        receive_response(mngr, result_rx)?;
+
+        mngr.tick_spinner(Instant::now());
+        renderer.draw_frame(mngr)?;
+    }
 
-        renderer.draw_frame(&mngr)?;
+    Ok(())
+}
+
+/// Shows the words and the match count of the latest responses from the worker, if
+/// any. Older responses still queued are skipped since they are already outdated.
+///
+/// The worker only stops once the queries stop being sent, so a disconnected channel
+/// means it panicked and no more words will ever arrive.
+fn receive_response(mngr: &mut AppManager, result_rx: &Receiver<QueryResponse>) -> Result<()> {
+    let mut latest = None;
+    loop {
+        match result_rx.try_recv() {
+            Ok(QueryResponse {
+                match_count: Some(count),
+                ..
+            }) => mngr.set_regex_match_count(count),
+            Ok(resp) => latest = Some(resp),
+            Err(TryRecvError::Empty) => break,
+            Err(TryRecvError::Disconnected) => {
+                return Err(anyhow!("the search worker stopped unexpectedly"))
+            }
+        }
     }
 
+    if let Some(resp) = latest {
+        mngr.set_output_words(resp.words);
+        mngr.set_query_elapsed(resp.elapsed);
+        mngr.set_search_partial(resp.partial);
+        mngr.set_searching(resp.partial);
+    }
     Ok(())
 }
 
@@ -72,18 +173,12 @@ impl AppManager {
             }
 
             let event = event::read()?;
-            let event = InputEvent::from(event);
-
-            input_updated = match event {
-                InputEvent::Exit => return Ok(true),
-                InputEvent::AppendCharToInputLetters(ch) => self.push_ch(ch),
-                InputEvent::BackSpace => self.pop_ch(),
-                InputEvent::NoOp => false,
-                InputEvent::SelectPanel(direction) => {
-                    self.select_panel(direction);
-                    false
-                }
-            };
+            let event = InputEvent::from_event(event, self.key_bindings());
+
+            match self.handle_event(event)? {
+                ControlFlow::Break(()) => return Ok(true),
+                ControlFlow::Continue(updated) => input_updated |= updated,
+            }
         }
 
         // Send inputs to worker
@@ -91,40 +186,526 @@ impl AppManager {
             return Ok(false);
         }
 
+        self.send_query(query_tx)?;
+
+        Ok(false)
+    }
+
+    /// Handles a single input event, breaking if an exit signal was received or else
+    /// continuing with whether the inputs of the query changed.
+    fn handle_event(&mut self, event: InputEvent) -> Result<ControlFlow<(), bool>> {
+        let input_updated = match event {
+            // Answering the quit confirmation is all that's allowed while it is shown
+            InputEvent::AppendCharToInputLetters(CONFIRM_QUIT_KEY) | InputEvent::Quit
+                if self.is_confirming_quit() =>
+            {
+                return Ok(ControlFlow::Break(()));
+            }
+            InputEvent::NoOp if self.is_confirming_quit() => false,
+            _ if self.is_confirming_quit() => {
+                self.set_confirm_quit(false);
+                false
+            }
+            InputEvent::Quit => {
+                self.set_confirm_quit(true);
+                false
+            }
+            // Only closing the help overlay is allowed while it is shown
+            InputEvent::Exit
+            | InputEvent::ToggleHelp
+            | InputEvent::AppendCharToInputLetters(HELP_KEY)
+                if self.is_help_shown() =>
+            {
+                self.toggle_help();
+                false
+            }
+            _ if self.is_help_shown() => false,
+            InputEvent::AppendCharToInputLetters(HELP_KEY)
+                if self.selected_panel().kind() != PanelKind::Regex =>
+            {
+                self.toggle_help();
+                false
+            }
+            InputEvent::ToggleHelp => {
+                self.toggle_help();
+                false
+            }
+            InputEvent::Exit if self.get_input_mode() == InputMode::Insert => {
+                self.set_input_mode(InputMode::Normal);
+                false
+            }
+            // Esc clears the selected input first so it can't quit by accident
+            InputEvent::Exit if self.clear_input() => true,
+            InputEvent::Exit => return Ok(ControlFlow::Break(())),
+            InputEvent::AppendCharToInputLetters(TOGGLE_SORT_KEY)
+                if self.selected_panel().kind() == PanelKind::Words =>
+            {
+                self.toggle_sort_mode();
+                true
+            }
+            InputEvent::AppendCharToInputLetters(TOGGLE_LAYOUT_KEY)
+                if self.selected_panel().kind() == PanelKind::Words =>
+            {
+                self.toggle_words_layout();
+                false
+            }
+            InputEvent::AppendCharToInputLetters(COPY_KEY) | InputEvent::Enter
+                if self.selected_panel().kind() == PanelKind::Words =>
+            {
+                if let Some(word) = self.get_selected_word() {
+                    copy_to_clipboard(word);
+                }
+                false
+            }
+            InputEvent::AppendCharToInputLetters(ch)
+                if self.get_input_mode() == InputMode::Normal =>
+            {
+                self.process_normal_key(ch);
+                false
+            }
+            InputEvent::AppendCharToInputLetters(ch) => self.push_ch(ch),
+            InputEvent::BackSpace => self.pop_ch(),
+            InputEvent::ClearInput => self.clear_input(),
+            InputEvent::DeleteWord => self.delete_word(),
+            InputEvent::ClearAll => self.clear_all(),
+            InputEvent::RecallHistory(direction) => self.recall_history(direction),
+            InputEvent::Enter if self.selected_panel().kind() == PanelKind::Letters => {
+                self.push_history();
+                false
+            }
+            InputEvent::Enter | InputEvent::NoOp => false,
+            InputEvent::Click { column, row } => {
+                let (width, height) = terminal::size()?;
+                let area = Rect::new(0, 0, width, height);
+                if let Some(kind) = panel_at(area, column, row) {
+                    self.select_panel_kind(kind);
+                }
+                false
+            }
+            InputEvent::SelectPanel(direction) => {
+                self.move_selection(direction);
+                false
+            }
+        };
+
+        Ok(ControlFlow::Continue(input_updated))
+    }
+
+    /// Handles a key typed in [`InputMode::Normal`], where keys navigate instead of
+    /// being typed into the inputs.
+    fn process_normal_key(&mut self, ch: char) {
+        match ch {
+            INSERT_KEY => self.set_input_mode(InputMode::Insert),
+            QUIT_KEY => self.set_confirm_quit(true),
+            'h' => self.move_selection(Direction::Left),
+            'j' => self.move_selection(Direction::Down),
+            'k' => self.move_selection(Direction::Up),
+            'l' => self.move_selection(Direction::Right),
+            HELP_KEY => self.toggle_help(),
+            _ => {}
+        }
+    }
+
+    /// Moves the word selection within the Words panel or else selects the next panel.
+    fn move_selection(&mut self, direction: Direction) {
+        let moved_selection =
+            self.selected_panel().kind() == PanelKind::Words && self.move_word_selection(direction);
+        if !moved_selection {
+            self.select_panel(direction);
+        }
+    }
+
+    /// Sends the current inputs to the worker unless the regex or length is invalid.
+    ///
+    /// Either field is enough to search: an empty rack with a regex still searches the
+    /// dictionary for matches. A changed regex is also counted against the whole
+    /// dictionary for the title of the Regex panel.
+    fn send_query(&mut self, query_tx: &Sender<QueryRequest>) -> Result<()> {
         if !self.is_regex_valid() {
-            return Ok(false);
+            return Ok(());
+        }
+
+        if self.take_regex_count_pending() {
+            self.queue_query(QueryRequest {
+                letters: "".into(),
+                regex: self.get_input_regex().into(),
+                min_len: None,
+                max_len: None,
+                sort: self.get_sort_mode(),
+                cancelled: Arc::default(),
+                count_only: true,
+            });
+        }
+
+        if let Some((min_len, max_len)) = self.get_length_bounds() {
+            self.set_searching(true);
+            self.queue_query(QueryRequest {
+                letters: self.get_input_letters().into(),
+                regex: self.get_input_regex().into(),
+                min_len,
+                max_len,
+                sort: self.get_sort_mode(),
+                cancelled: Arc::default(),
+                count_only: false,
+            });
         }
 
-        if let Err(err) = query_tx.try_send(QueryRequest {
-            letters: self.get_input_letters().into(),
-            regex: self.get_input_regex().into(),
-        }) {
-            match err {
-                TrySendError::Full(_) => {}
-                TrySendError::Disconnected(err) => {
+        self.send_queued_queries(query_tx)
+    }
+
+    /// Sends the queued queries without waiting, cancelling the searches they replace.
+    ///
+    /// A query the worker can't take yet, because the channel is full, stays queued to
+    /// be sent again on the next call.
+    fn send_queued_queries(&mut self, query_tx: &Sender<QueryRequest>) -> Result<()> {
+        for count_only in [true, false] {
+            let Some(query) = self.take_queued_query(count_only) else {
+                continue;
+            };
+
+            let cancelled = query.cancelled.clone();
+            match query_tx.try_send(query) {
+                Ok(()) => self.query_sent(cancelled, count_only),
+                Err(TrySendError::Full(query)) => self.queue_query(query),
+                Err(TrySendError::Disconnected(err)) => {
                     return Err(anyhow!("Worker unexpectedly disconnected: {err:?}"))
                 }
             }
         }
 
-        Ok(false)
+        Ok(())
     }
 }
 
-impl From<crossterm::event::Event> for InputEvent {
-    fn from(ev: crossterm::event::Event) -> Self {
-        match ev {
-            Event::Key(key_event) => match key_event.code {
-                KeyCode::Backspace => Self::BackSpace,
-                KeyCode::Char(ch) => Self::AppendCharToInputLetters(ch),
-                KeyCode::Esc => Self::Exit,
-                KeyCode::Left => Self::SelectPanel(Direction::Left),
-                KeyCode::Right => Self::SelectPanel(Direction::Right),
-                KeyCode::Up => Self::SelectPanel(Direction::Up),
-                KeyCode::Down => Self::SelectPanel(Direction::Down),
-                _ => Self::NoOp,
-            },
+impl InputEvent {
+    /// Maps the terminal event to an [`InputEvent`], using the [`KeyBindings`] for the
+    /// keys that can be rebound.
+    fn from_event(ev: Event, bindings: &KeyBindings) -> Self {
+        let key_event = match ev {
+            Event::Key(key_event) => key_event,
+            Event::Mouse(MouseEvent {
+                kind: MouseEventKind::Down(MouseButton::Left),
+                column,
+                row,
+                ..
+            }) => return Self::Click { column, row },
+            _ => return Self::NoOp,
+        };
+
+        // Shift only changes the typed character, but other modifiers make shortcuts.
+        // AltGr, used to type characters like `[` on some keyboards, is reported as
+        // Ctrl+Alt on Windows.
+        let alt_gr = KeyModifiers::CONTROL | KeyModifiers::ALT;
+        if !key_event.modifiers.contains(alt_gr) {
+            if key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                return match key_event.code {
+                    KeyCode::Char('c') => Self::Quit,
+                    KeyCode::Char('u') => Self::ClearInput,
+                    KeyCode::Char('w') => Self::DeleteWord,
+                    KeyCode::Char('l') => Self::ClearAll,
+                    KeyCode::Char('p') => Self::RecallHistory(Direction::Up),
+                    KeyCode::Char('n') => Self::RecallHistory(Direction::Down),
+                    _ => Self::NoOp,
+                };
+            }
+            if key_event.modifiers.contains(KeyModifiers::ALT) {
+                return Self::NoOp;
+            }
+        }
+
+        if let Some(action) = bindings.action(key_event.code) {
+            return match action {
+                Action::Exit => Self::Exit,
+                Action::Up => Self::SelectPanel(Direction::Up),
+                Action::Down => Self::SelectPanel(Direction::Down),
+                Action::Left => Self::SelectPanel(Direction::Left),
+                Action::Right => Self::SelectPanel(Direction::Right),
+                Action::Backspace => Self::BackSpace,
+            };
+        }
+
+        match key_event.code {
+            KeyCode::Char(ch) => Self::AppendCharToInputLetters(ch),
+            KeyCode::Enter => Self::Enter,
+            KeyCode::F(1) => Self::ToggleHelp,
             _ => Self::NoOp,
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::Key;
+    use crossbeam::channel;
+    use crossterm::event::KeyEvent;
+    use pretty_assertions::assert_eq;
+    use std::sync::atomic::Ordering;
+
+    fn key(code: KeyCode) -> Event {
+        Event::Key(KeyEvent::from(code))
+    }
+
+    #[test]
+    fn test_regex_only_query_is_sent() {
+        let (query_tx, query_rx) = channel::bounded(1);
+        let mut mngr = AppManager::default();
+        mngr.select_panel(Direction::Right);
+        "^ca".chars().for_each(|ch| {
+            mngr.push_ch(ch);
+        });
+
+        mngr.send_query(&query_tx).expect("worker is connected");
+        let query = query_rx.try_recv().expect("a query was sent");
+
+        assert_eq!(&*query.letters, "");
+        assert_eq!(&*query.regex, "^ca");
+    }
+
+    #[test]
+    fn test_changed_regex_is_counted() {
+        let (query_tx, query_rx) = channel::bounded(3);
+        let mut mngr = AppManager::default();
+        mngr.push_ch('a');
+        mngr.select_panel(Direction::Right);
+        mngr.push_ch('c');
+
+        mngr.send_query(&query_tx).expect("worker is connected");
+        mngr.send_query(&query_tx).expect("worker is connected");
+        let queries = query_rx.try_iter().collect::<Vec<_>>();
+
+        assert_eq!(queries.len(), 3);
+        assert!(queries[0].count_only);
+        assert_eq!(&*queries[0].letters, "");
+        assert_eq!(&*queries[0].regex, "c");
+        assert!(!queries[1].count_only);
+        assert!(!queries[2].count_only);
+    }
+
+    #[test]
+    fn test_query_is_retried_once_the_channel_has_room() {
+        let (query_tx, query_rx) = channel::bounded(1);
+        let mut mngr = AppManager::default();
+
+        mngr.push_ch('c');
+        mngr.send_query(&query_tx).expect("worker is connected");
+        mngr.push_ch('a');
+        mngr.send_query(&query_tx).expect("worker is connected");
+        mngr.push_ch('t');
+        mngr.send_query(&query_tx).expect("worker is connected");
+
+        // The running search is only cancelled once a newer query was taken
+        let first = query_rx.try_recv().expect("the first query was sent");
+        assert_eq!(&*first.letters, "c");
+        assert!(!first.cancelled.load(Ordering::Relaxed));
+
+        mngr.send_queued_queries(&query_tx)
+            .expect("worker is connected");
+        let last = query_rx.try_recv().expect("the last query was sent");
+
+        assert_eq!(&*last.letters, "cat");
+        assert!(first.cancelled.load(Ordering::Relaxed));
+        assert!(query_rx.try_recv().is_err());
+        assert!(mngr.is_searching());
+    }
+
+    #[test]
+    fn test_invalid_regex_query_is_not_sent() {
+        let (query_tx, query_rx) = channel::bounded(1);
+        let mut mngr = AppManager::default();
+        mngr.push_ch('a');
+        mngr.select_panel(Direction::Right);
+        mngr.push_ch('(');
+
+        mngr.send_query(&query_tx).expect("worker is connected");
+
+        assert!(query_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_disconnected_worker_is_an_error() {
+        let mut mngr = AppManager::default();
+        let (result_tx, result_rx) = channel::bounded(1);
+
+        assert!(receive_response(&mut mngr, &result_rx).is_ok());
+
+        drop(result_tx);
+        assert!(receive_response(&mut mngr, &result_rx).is_err());
+    }
+
+    #[test]
+    fn test_only_the_newest_response_is_shown() {
+        let mut mngr = AppManager::default();
+        let (result_tx, result_rx) = channel::bounded(3);
+        for (word, partial) in [("a", true), ("at", true), ("cat", false)] {
+            result_tx
+                .send(QueryResponse {
+                    words: vec![(word.to_string(), 1)],
+                    elapsed: Duration::from_millis(1),
+                    partial,
+                    match_count: None,
+                })
+                .unwrap();
+        }
+
+        receive_response(&mut mngr, &result_rx).expect("worker is connected");
+
+        assert_eq!(mngr.get_ouput_words(), [("cat".to_string(), 1)]);
+        assert!(!mngr.is_search_partial());
+        assert!(result_rx.is_empty());
+    }
+
+    #[test]
+    fn test_input_mode_transitions() {
+        let mut mngr = AppManager::default();
+        assert_eq!(mngr.get_input_mode(), InputMode::Insert);
+
+        let flow = mngr.handle_event(InputEvent::Exit).unwrap();
+        assert_eq!(flow, ControlFlow::Continue(false));
+        assert_eq!(mngr.get_input_mode(), InputMode::Normal);
+
+        let flow = mngr.handle_event(InputEvent::AppendCharToInputLetters('i'));
+        assert_eq!(flow.unwrap(), ControlFlow::Continue(false));
+        assert_eq!(mngr.get_input_mode(), InputMode::Insert);
+        assert_eq!(mngr.get_input_letters(), "");
+    }
+
+    #[test]
+    fn test_esc_only_exits_in_normal_mode() {
+        let mut mngr = AppManager::default();
+
+        mngr.set_input_mode(InputMode::Insert);
+        assert!(mngr.handle_event(InputEvent::Exit).unwrap().is_continue());
+
+        mngr.set_input_mode(InputMode::Normal);
+        assert!(mngr.handle_event(InputEvent::Exit).unwrap().is_break());
+    }
+
+    #[test]
+    fn test_esc_clears_the_input_before_exiting() {
+        let mut mngr = AppManager::default();
+        mngr.push_ch('a');
+        mngr.set_input_mode(InputMode::Normal);
+
+        let flow = mngr.handle_event(InputEvent::Exit).unwrap();
+        assert_eq!(flow, ControlFlow::Continue(true));
+        assert_eq!(mngr.get_input_letters(), "");
+
+        assert!(mngr.handle_event(InputEvent::Exit).unwrap().is_break());
+    }
+
+    #[test]
+    fn test_normal_mode_keys_navigate() {
+        let mut mngr = AppManager::default();
+        mngr.set_input_mode(InputMode::Normal);
+
+        let flow = mngr.handle_event(InputEvent::AppendCharToInputLetters('l'));
+        assert_eq!(flow.unwrap(), ControlFlow::Continue(false));
+        assert_eq!(mngr.selected_panel().kind(), PanelKind::Regex);
+        assert_eq!(mngr.get_input_regex(), "");
+
+        mngr.set_input_mode(InputMode::Insert);
+        let flow = mngr.handle_event(InputEvent::AppendCharToInputLetters('l'));
+        assert_eq!(flow.unwrap(), ControlFlow::Continue(true));
+        assert_eq!(mngr.get_input_regex(), "l");
+    }
+
+    #[test]
+    fn test_quit_confirmation() {
+        let mut mngr = AppManager::default();
+
+        let flow = mngr.handle_event(InputEvent::Quit).unwrap();
+        assert_eq!(flow, ControlFlow::Continue(false));
+        assert!(mngr.is_confirming_quit());
+
+        let flow = mngr.handle_event(InputEvent::AppendCharToInputLetters('n'));
+        assert_eq!(flow.unwrap(), ControlFlow::Continue(false));
+        assert!(!mngr.is_confirming_quit());
+        assert_eq!(mngr.get_input_letters(), "");
+
+        mngr.set_input_mode(InputMode::Normal);
+        let flow = mngr.handle_event(InputEvent::AppendCharToInputLetters('q'));
+        assert_eq!(flow.unwrap(), ControlFlow::Continue(false));
+        assert!(mngr.is_confirming_quit());
+
+        let flow = mngr.handle_event(InputEvent::AppendCharToInputLetters('y'));
+        assert!(flow.unwrap().is_break());
+    }
+
+    #[test]
+    fn test_key_modifiers() {
+        let bindings = KeyBindings::default();
+        let with = |ch, modifiers| Event::Key(KeyEvent::new(KeyCode::Char(ch), modifiers));
+
+        assert_eq!(
+            InputEvent::from_event(with('c', KeyModifiers::CONTROL), &bindings),
+            InputEvent::Quit
+        );
+        assert_eq!(
+            InputEvent::from_event(with('u', KeyModifiers::CONTROL), &bindings),
+            InputEvent::ClearInput
+        );
+        assert_eq!(
+            InputEvent::from_event(with('w', KeyModifiers::CONTROL), &bindings),
+            InputEvent::DeleteWord
+        );
+        assert_eq!(
+            InputEvent::from_event(with('l', KeyModifiers::CONTROL), &bindings),
+            InputEvent::ClearAll
+        );
+        assert_eq!(
+            InputEvent::from_event(with('x', KeyModifiers::ALT), &bindings),
+            InputEvent::NoOp
+        );
+        assert_eq!(
+            InputEvent::from_event(
+                with('[', KeyModifiers::CONTROL | KeyModifiers::ALT),
+                &bindings
+            ),
+            InputEvent::AppendCharToInputLetters('[')
+        );
+        assert_eq!(
+            InputEvent::from_event(with('A', KeyModifiers::SHIFT), &bindings),
+            InputEvent::AppendCharToInputLetters('A')
+        );
+    }
+
+    #[test]
+    fn test_ctrl_u_clears_the_input() {
+        let mut mngr = AppManager::default();
+        "radar".chars().for_each(|ch| {
+            mngr.push_ch(ch);
+        });
+
+        let flow = mngr.handle_event(InputEvent::ClearInput).unwrap();
+        assert_eq!(flow, ControlFlow::Continue(true));
+        assert_eq!(mngr.get_input_letters(), "");
+    }
+
+    #[test]
+    fn test_rebound_key() {
+        let defaults = KeyBindings::default();
+        let bindings = KeyBindings {
+            exit: Key(KeyCode::Char('q')),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            InputEvent::from_event(key(KeyCode::Char('q')), &defaults),
+            InputEvent::AppendCharToInputLetters('q')
+        );
+        assert_eq!(
+            InputEvent::from_event(key(KeyCode::Esc), &defaults),
+            InputEvent::Exit
+        );
+
+        assert_eq!(
+            InputEvent::from_event(key(KeyCode::Char('q')), &bindings),
+            InputEvent::Exit
+        );
+        assert_eq!(
+            InputEvent::from_event(key(KeyCode::Esc), &bindings),
+            InputEvent::NoOp
+        );
+    }
+}
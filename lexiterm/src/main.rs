@@ -1,37 +1,80 @@
 mod app_manager;
+mod cli;
+mod config;
 mod input_processing;
+mod one_shot;
 mod search_worker;
+mod session;
 mod tui_renderer;
 
 use anyhow::{anyhow, Result};
 use app_manager::*;
+use clap::Parser;
+use cli::Args;
+use config::Config;
 use crossbeam::channel;
 use input_processing::listen_and_process;
 use lexicon::ScoredWordTrie;
 use search_worker::{search_worker, QueryRequest, QueryResponse};
-use std::path::Path;
+use session::Session;
+use std::process::ExitCode;
 use std::thread;
 use tui_renderer::*;
 
-fn main() -> Result<()> {
-    let (query_tx, query_rx) = channel::bounded::<QueryRequest>(100);
-    let (result_tx, result_rx) = channel::bounded::<QueryResponse>(30);
+fn main() -> Result<ExitCode> {
+    let args = Args::parse();
 
-    let words_file_path = Path::new("./words.txt");
-    let scores_file_path = Path::new("./char_scores.txt");
-    let word_trie = ScoredWordTrie::new_from_files(words_file_path, scores_file_path)?;
+    let word_trie = match ScoredWordTrie::new_from_files(&args.words, &args.scores) {
+        Ok(word_trie) => word_trie,
+        Err(err) => {
+            eprintln!("error: {err}");
+            eprintln!("hint: use `--words <PATH>` and `--scores <PATH>` to choose the files");
+            return Ok(ExitCode::FAILURE);
+        }
+    };
+
+    let config = match args.config.clone().or_else(Config::default_path) {
+        Some(path) => match Config::load(&path) {
+            Ok(config) => config,
+            Err(err) => {
+                eprintln!("error: {err:#}");
+                return Ok(ExitCode::FAILURE);
+            }
+        },
+        None => Config::default(),
+    };
+
+    if args.query.is_some() {
+        let mut stdout = std::io::stdout().lock();
+        one_shot::run_query(&word_trie, &args, &config.result_format, &mut stdout)?;
+        return Ok(ExitCode::SUCCESS);
+    }
+
+    let (query_tx, query_rx) = channel::bounded::<QueryRequest>(config.channels.queries);
+    let (result_tx, result_rx) = channel::bounded::<QueryResponse>(config.channels.results);
 
     let search_handle = thread::spawn(move || {
         search_worker(word_trie, query_rx, result_tx);
     });
 
-    let state_mngr = AppManager::default();
+    let mut state_mngr = AppManager::default();
+    state_mngr.set_no_color(args.is_no_color());
+    state_mngr.set_key_bindings(config.keybindings);
+    state_mngr.set_result_format(config.result_format);
+    state_mngr.set_theme(config.theme);
+    let session_path = Session::default_path();
+    if let Some(session) = session_path.as_deref().and_then(Session::load) {
+        state_mngr.restore_session(session);
+    }
     let tui_renderer = TuiRenderer::default();
 
-    let listener_result = listen_and_process(state_mngr, tui_renderer, &query_tx, &result_rx);
+    let listener_result = listen_and_process(&mut state_mngr, tui_renderer, &query_tx, &result_rx);
 
-    // Ensure worker sees EOF and exits
+    // Abort the search still running, if any, and ensure worker sees EOF and exits,
+    // even if it is waiting for a result to be read
+    state_mngr.cancel_searches();
     drop(query_tx);
+    drop(result_rx);
     ratatui::restore();
 
     search_handle
@@ -40,5 +83,11 @@ fn main() -> Result<()> {
 
     listener_result?;
 
-    Ok(())
+    if let Some(path) = session_path {
+        if let Err(err) = state_mngr.session().save(&path) {
+            eprintln!("warning: the session wasn't saved: {err:#}");
+        }
+    }
+
+    Ok(ExitCode::SUCCESS)
 }
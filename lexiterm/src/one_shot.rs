@@ -0,0 +1,60 @@
+//! Runs a single search query without starting the TUI.
+//!
+//! This makes lexiterm scriptable: the results are printed to stdout, either one per
+//! line using the configured [`ResultFormat`] or as a JSON array.
+
+use crate::cli::{Args, OutputFormat, SortOrder};
+use crate::config::ResultFormat;
+use anyhow::Result;
+use lexicon::ScoredWordTrie;
+use serde::Serialize;
+use std::io::Write;
+
+#[derive(Serialize)]
+struct WordResult<'a> {
+    word: &'a str,
+    score: u32,
+}
+
+/// Searches the [`ScoredWordTrie`] using the query from the [`Args`] and writes the
+/// results to `out`.
+pub fn run_query(
+    word_trie: &ScoredWordTrie,
+    args: &Args,
+    result_format: &ResultFormat,
+    out: &mut impl Write,
+) -> Result<()> {
+    let letters = args.query.as_deref().unwrap_or_default();
+
+    let mut words = match args.regex.as_deref() {
+        Some(expr) => word_trie.get_word_matches(letters, expr)?,
+        None => word_trie.get_words(letters),
+    };
+
+    if args.sort == SortOrder::Alpha {
+        words.sort();
+    }
+
+    let limit = args.limit.unwrap_or(usize::MAX);
+    let words = words.iter().take(limit);
+
+    match args.format {
+        OutputFormat::Plain => {
+            for (word, score) in words {
+                writeln!(out, "{}", result_format.format(word, *score))?;
+            }
+        }
+        OutputFormat::Json => {
+            let results = words
+                .map(|(word, score)| WordResult {
+                    word,
+                    score: *score,
+                })
+                .collect::<Vec<_>>();
+            serde_json::to_writer(&mut *out, &results)?;
+            writeln!(out)?;
+        }
+    }
+
+    Ok(())
+}
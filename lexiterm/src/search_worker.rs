@@ -1,81 +1,462 @@
 //! Handles background search queries and sends results back to the main thread.
 //!
-//! This worker listens for search queries via a [`Receiver<String>`], processes them
-//! using a [`WordTrie`], and sends the results back via a [`Sender<Vec<String>>`].
+//! This worker listens for search queries via a [`Receiver<QueryRequest>`], processes
+//! them using a [`ScoredWordTrie`], and sends the words and their scores back via a
+//! [`Sender<QueryResponse>`].
 //!
 //! The worker implements **debouncing**, ensuring that rapid consecutive queries
-//! are ignored except for the most recent one within a short time window.
+//! are ignored except for the most recent one within a short time window. A search
+//! that is already running is abandoned once its [`QueryRequest::cancelled`] flag is
+//! set, which happens as soon as a newer query is sent.
 
+use crate::app_manager::SortMode;
 use crossbeam::channel::{Receiver, Sender};
-use lexicon::ScoredWordTrie;
-use std::time::Duration;
+use lexicon::{ResultOrdering, ScoredWordTrie, SearchSession};
+use regex::Regex;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// The debounce duration for processing search queries.
 ///
 /// If a new query arrives within this duration, the previous query is discarded.
 static DEBOUNCE_DUR: Duration = Duration::from_millis(100);
 
+/// How often the words found so far are sent while a search is still running.
+static PARTIAL_RESULTS_INTERVAL: Duration = Duration::from_millis(100);
+
 #[derive(Debug)]
 pub struct QueryRequest {
     pub letters: Box<str>,
     pub regex: Box<str>,
+    /// The minimum number of letters of the words, if any.
+    pub min_len: Option<usize>,
+    /// The maximum number of letters of the words, if any.
+    pub max_len: Option<usize>,
+    pub sort: SortMode,
+    /// Set to `true` once a newer query is sent so the search can be abandoned.
+    pub cancelled: Arc<AtomicBool>,
+    /// Only count the words matching the regex instead of sending them back.
+    ///
+    /// Count-only queries are debounced separately so they don't replace the search
+    /// sent right after them.
+    pub count_only: bool,
 }
 
 #[derive(Debug)]
 pub struct QueryResponse {
-    pub words: Vec<String>,
+    /// The words found and their scores.
+    pub words: Vec<(String, u32)>,
+    /// How long the search itself took, excluding the debounce window.
+    pub elapsed: Duration,
+    /// Whether the search is still running and more words may follow.
+    pub partial: bool,
+    /// The number of matching words, set instead of the words for count-only queries.
+    pub match_count: Option<usize>,
 }
 
 /// Listens for incoming search queries and processes only the most recent one.
 ///
 /// This function continuously receives search queries from `query_rx`, applies
 /// **debouncing** to ignore outdated queries, processes the latest one using a
-/// [`WordTrie`], and then sends the sorted results back through `result_tx`.
+/// [`ScoredWordTrie`], and then sends the sorted `(word, score)` results back through
+/// `result_tx`.
+///
+/// Long searches also send the sorted `(word, score)` pairs found so far as partial
+/// responses before the final one.
 pub fn search_worker(
     word_trie: ScoredWordTrie,
     query_rx: Receiver<QueryRequest>,
     result_tx: Sender<QueryResponse>,
 ) {
+    search_worker_with_interval(word_trie, query_rx, result_tx, PARTIAL_RESULTS_INTERVAL)
+}
+
+fn search_worker_with_interval(
+    word_trie: ScoredWordTrie,
+    query_rx: Receiver<QueryRequest>,
+    result_tx: Sender<QueryResponse>,
+    partial_interval: Duration,
+) {
+    let mut worker = Worker {
+        word_trie,
+        cached_regex: None,
+        session: SearchSession::default(),
+        result_tx,
+        partial_interval,
+    };
+
     loop {
         // Block until at least one query arrives
-        let Ok(mut query) = query_rx.recv() else {
+        let Ok(query) = query_rx.recv() else {
             return;
         };
 
         // Keep receiving queries within the debounce window
+        let mut search = None;
+        let mut count = None;
+        let mut keep_latest = |query: QueryRequest| {
+            if query.count_only {
+                count = Some(query);
+            } else {
+                search = Some(query);
+            }
+        };
+        keep_latest(query);
         while let Ok(new_query) = query_rx.recv_timeout(DEBOUNCE_DUR) {
-            query = new_query
+            keep_latest(new_query);
+        }
+
+        // Process only the most recent queries
+        let sent = search.is_none_or(|query| worker.search(query))
+            && count.is_none_or(|query| worker.count(query));
+        if !sent {
+            break;
         }
+    }
+}
+
+struct Worker {
+    word_trie: ScoredWordTrie,
+    /// The regex is only recompiled when its text changes between queries
+    cached_regex: Option<Regex>,
+    /// Typing a letter only extends the previous search instead of starting over
+    session: SearchSession,
+    result_tx: Sender<QueryResponse>,
+    partial_interval: Duration,
+}
+
+impl Worker {
+    /// Searches for the words of the query and sends them back, returning `false` if
+    /// the main thread stopped listening.
+    fn search(&mut self, query: QueryRequest) -> bool {
+        self.word_trie.ordering = match query.sort {
+            SortMode::Score => ResultOrdering::TieBreak,
+            SortMode::Alpha => ResultOrdering::Alphabetical,
+        };
 
-        // Process only the most recent query
+        let start = Instant::now();
+        let result_tx = &self.result_tx;
+        // Partial responses are dropped rather than waited on if the channel is full
+        let send_partial = |words: Vec<(String, u32)>| {
+            let _ = result_tx.try_send(QueryResponse {
+                words: filter_by_length(&query, words),
+                elapsed: start.elapsed(),
+                partial: true,
+                match_count: None,
+            });
+        };
         let words = if query.regex.is_empty() {
-            word_trie
-                .get_words(&query.letters)
-                .into_iter()
-                .map(|(word, score)| format!("{}:{}", word, score))
-                .collect::<Vec<_>>()
+            self.word_trie.get_words_in_session_with_progress(
+                &mut self.session,
+                &query.letters,
+                &query.cancelled,
+                self.partial_interval,
+                send_partial,
+            )
         } else {
-            let Ok(words) = word_trie
-                .get_word_matches(&query.letters, &query.regex)
-                .map(|words| {
-                    words
-                        .into_iter()
-                        .map(|(word, score)| format!("{}:{}", word, score))
-                        .collect::<Vec<_>>()
-                })
-            else {
-                // get_word_mataches will only return an error if the regex is invalid
-                // but we already make sure that the regex is valid so we can just ignore
-                // the Result::Err
-                continue;
+            let Some(re) = compile_cached(&mut self.cached_regex, &query.regex) else {
+                return true;
             };
-            words
+
+            self.word_trie.get_word_matches_with_progress(
+                &query.letters,
+                re,
+                &query.cancelled,
+                self.partial_interval,
+                send_partial,
+            )
         };
 
-        let resp = QueryResponse { words };
+        // A newer query is on its way so these results are already stale
+        let Some(words) = words else {
+            return true;
+        };
 
-        if result_tx.send(resp).is_err() {
-            break;
+        let resp = QueryResponse {
+            words: filter_by_length(&query, words),
+            elapsed: start.elapsed(),
+            partial: false,
+            match_count: None,
+        };
+
+        self.result_tx.send(resp).is_ok()
+    }
+
+    /// Counts the words matching the regex of the query and sends the count back,
+    /// returning `false` if the main thread stopped listening.
+    fn count(&mut self, query: QueryRequest) -> bool {
+        let start = Instant::now();
+        let Some(re) = compile_cached(&mut self.cached_regex, &query.regex) else {
+            return true;
+        };
+
+        let Some(count) =
+            self.word_trie
+                .word_trie
+                .count_word_matches(&query.letters, re, &query.cancelled)
+        else {
+            return true;
+        };
+
+        let resp = QueryResponse {
+            words: Vec::new(),
+            elapsed: start.elapsed(),
+            partial: false,
+            match_count: Some(count),
+        };
+
+        self.result_tx.send(resp).is_ok()
+    }
+}
+
+/// Returns the compiled regex, only compiling it if it differs from the cached one.
+///
+/// Regex::new will only return an error if the regex is invalid but we already make
+/// sure that the regex is valid so we can just ignore the Result::Err
+fn compile_cached<'a>(cached_regex: &'a mut Option<Regex>, expr: &str) -> Option<&'a Regex> {
+    if cached_regex.as_ref().is_none_or(|re| re.as_str() != expr) {
+        *cached_regex = Some(Regex::new(expr).ok()?);
+    }
+
+    cached_regex.as_ref()
+}
+
+/// Keeps the words and their scores that are within the length bounds of the query.
+fn filter_by_length(query: &QueryRequest, words: Vec<(String, u32)>) -> Vec<(String, u32)> {
+    words
+        .into_iter()
+        .filter(|(word, _)| {
+            let len = word.chars().count();
+            query.min_len.is_none_or(|min| len >= min) && query.max_len.is_none_or(|max| len <= max)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crossbeam::channel;
+    use lexicon::WordTrie;
+    use std::collections::HashMap;
+    use std::thread::{self, JoinHandle};
+
+    /// Builds a query searching the letters, with no other filter.
+    fn query(letters: &str) -> QueryRequest {
+        QueryRequest {
+            letters: letters.into(),
+            regex: "".into(),
+            min_len: None,
+            max_len: None,
+            sort: SortMode::Score,
+            cancelled: Arc::default(),
+            count_only: false,
+        }
+    }
+
+    /// Wraps the words in a [`ScoredWordTrie`] where every letter scores zero.
+    fn scored_trie<S: AsRef<str>>(words: impl IntoIterator<Item = S>) -> ScoredWordTrie {
+        let mut word_trie = WordTrie::default();
+        word_trie.insert_all(words);
+        ScoredWordTrie {
+            word_trie,
+            ..Default::default()
+        }
+    }
+
+    /// Returns every word of up to `max_len` letters made out of the letters.
+    fn every_word(letters: &str, max_len: usize) -> Vec<String> {
+        let mut words = vec![String::new()];
+        let mut all_words = Vec::new();
+        for _ in 0..max_len {
+            words = words
+                .iter()
+                .flat_map(|word| letters.chars().map(move |ch| format!("{word}{ch}")))
+                .collect();
+            all_words.extend(words.iter().cloned());
+        }
+        all_words
+    }
+
+    /// Spawns a worker searching the trie through channels of the given capacity, or
+    /// through unbounded channels if there is none.
+    fn spawn_worker(
+        word_trie: ScoredWordTrie,
+        capacity: Option<usize>,
+        partial_interval: Duration,
+    ) -> (
+        Sender<QueryRequest>,
+        Receiver<QueryResponse>,
+        JoinHandle<()>,
+    ) {
+        fn new_channel<T>(capacity: Option<usize>) -> (Sender<T>, Receiver<T>) {
+            capacity.map_or_else(channel::unbounded, channel::bounded)
+        }
+
+        let (query_tx, query_rx) = new_channel(capacity);
+        let (result_tx, result_rx) = new_channel(capacity);
+        let handle = thread::spawn(move || {
+            search_worker_with_interval(word_trie, query_rx, result_tx, partial_interval)
+        });
+
+        (query_tx, result_rx, handle)
+    }
+
+    #[test]
+    fn test_response_reports_elapsed_time() {
+        let word_trie = ScoredWordTrie {
+            score_map: HashMap::from([('c', 3), ('a', 1), ('t', 1)]),
+            ..scored_trie(["cat", "act", "tac"])
+        };
+        let (query_tx, result_rx, handle) =
+            spawn_worker(word_trie, Some(1), PARTIAL_RESULTS_INTERVAL);
+
+        query_tx.send(query("cat")).expect("send query");
+        let resp = result_rx.recv().expect("receive response");
+        drop(query_tx);
+        handle.join().expect("worker exits");
+
+        assert_eq!(resp.words.len(), 3);
+        assert!(resp.elapsed > Duration::ZERO);
+        assert!(!resp.partial);
+    }
+
+    #[test]
+    fn test_rendezvous_channels_hand_off_one_to_one() {
+        let word_trie = scored_trie(["cat", "act", "at"]);
+        let (query_tx, result_rx, handle) =
+            spawn_worker(word_trie, Some(0), PARTIAL_RESULTS_INTERVAL);
+
+        for (letters, count) in [("cat", 3), ("at", 1)] {
+            query_tx
+                .send(query(letters))
+                .expect("the worker takes the query");
+            let resp = result_rx.recv().expect("receive response");
+
+            assert_eq!(resp.words.len(), count);
+            assert!(result_rx.try_recv().is_err());
         }
+
+        drop(query_tx);
+        handle.join().expect("worker exits");
+    }
+
+    #[test]
+    fn test_partial_responses_are_streamed() {
+        let word_trie = scored_trie(every_word("abcde", 5));
+        let (query_tx, result_rx, handle) = spawn_worker(word_trie, None, Duration::ZERO);
+
+        query_tx.send(query("*****")).expect("send query");
+        let mut responses = Vec::new();
+        loop {
+            let resp = result_rx.recv().expect("receive response");
+            let partial = resp.partial;
+            responses.push(resp);
+            if !partial {
+                break;
+            }
+        }
+        drop(query_tx);
+        handle.join().expect("worker exits");
+
+        assert!(responses.len() > 1);
+        let last = responses.last().expect("final response");
+        assert_eq!(last.words.len(), 5 + 25 + 125 + 625 + 3125);
+        assert!(responses
+            .iter()
+            .all(|resp| resp.words.len() <= last.words.len()));
+    }
+
+    #[test]
+    fn test_cancelled_query_sends_no_response() {
+        let word_trie = scored_trie(["cat", "act", "tac"]);
+        let (query_tx, result_rx, handle) =
+            spawn_worker(word_trie, Some(1), PARTIAL_RESULTS_INTERVAL);
+
+        query_tx
+            .send(QueryRequest {
+                cancelled: Arc::new(AtomicBool::new(true)),
+                ..query("***")
+            })
+            .expect("send query");
+        assert!(result_rx.recv_timeout(Duration::from_millis(500)).is_err());
+
+        query_tx.send(query("cat")).expect("send query");
+        let resp = result_rx.recv().expect("receive response");
+        drop(query_tx);
+        handle.join().expect("worker exits");
+
+        assert_eq!(resp.words.len(), 3);
+    }
+
+    #[test]
+    fn test_cancelled_search_stops_without_responding() {
+        let word_trie = scored_trie(["cat", "act", "tac"]);
+        let (query_tx, result_rx, handle) = spawn_worker(word_trie, Some(1), Duration::MAX);
+
+        let query = query("cat");
+        let cancelled = query.cancelled.clone();
+        query_tx.send(query).expect("send query");
+        // Quitting cancels the search before the worker is done debouncing it
+        cancelled.store(true, std::sync::atomic::Ordering::Relaxed);
+        drop(query_tx);
+        handle.join().expect("worker exits");
+
+        assert!(result_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_search_cancelled_while_running_stops_without_responding() {
+        let word_trie = scored_trie(every_word("abcdef", 6));
+        let (query_tx, result_rx, handle) = spawn_worker(word_trie, None, Duration::ZERO);
+
+        let query = query("******");
+        let cancelled = query.cancelled.clone();
+        query_tx.send(query).expect("send query");
+        // A partial response means that the trie is being searched
+        let resp = result_rx.recv().expect("receive response");
+        assert!(resp.partial);
+        cancelled.store(true, std::sync::atomic::Ordering::Relaxed);
+        drop(query_tx);
+        handle.join().expect("worker exits");
+
+        assert!(result_rx.try_iter().all(|resp| resp.partial));
+    }
+
+    #[test]
+    fn test_worker_exits_once_results_are_no_longer_read() {
+        let word_trie = scored_trie(["cat", "act", "tac"]);
+        let (query_tx, result_rx, handle) =
+            spawn_worker(word_trie, Some(0), PARTIAL_RESULTS_INTERVAL);
+
+        query_tx.send(query("cat")).expect("send query");
+        // The worker waits to hand off the result until the receiver is dropped
+        drop(query_tx);
+        drop(result_rx);
+        handle.join().expect("worker exits");
+    }
+
+    #[test]
+    fn test_count_only_query() {
+        let word_trie = scored_trie(["cat", "car", "cart", "dart", "act"]);
+        let (query_tx, result_rx, handle) =
+            spawn_worker(word_trie, Some(2), PARTIAL_RESULTS_INTERVAL);
+
+        let query = |letters: &str, count_only| QueryRequest {
+            regex: "^ca".into(),
+            count_only,
+            ..query(letters)
+        };
+        query_tx.send(query("", true)).expect("send query");
+        query_tx.send(query("tac", false)).expect("send query");
+        let search = result_rx.recv().expect("receive response");
+        let count = result_rx.recv().expect("receive response");
+        drop(query_tx);
+        handle.join().expect("worker exits");
+
+        assert_eq!(search.words, [("cat".to_string(), 0)]);
+        assert_eq!(search.match_count, None);
+        assert_eq!(count.words, Vec::new());
+        assert_eq!(count.match_count, Some(3));
     }
 }
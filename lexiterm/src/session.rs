@@ -0,0 +1,68 @@
+//! Keeps the rack and regex of the last run so they don't have to be typed again.
+//!
+//! The session is saved as a small TOML file when lexiterm exits and restored at the
+//! next start. A missing or corrupt session file is ignored.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Session {
+    pub letters: String,
+    pub regex: String,
+}
+
+impl Session {
+    /// Returns the path of the session file, e.g. `~/.local/state/lexiterm/session.toml`
+    /// on Linux.
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::state_dir()
+            .or_else(dirs::data_local_dir)
+            .map(|dir| dir.join("lexiterm").join("session.toml"))
+    }
+
+    /// Loads the session from the file, or returns `None` if it is missing or can't be
+    /// read.
+    pub fn load(path: &Path) -> Option<Self> {
+        let content = fs::read_to_string(path).ok()?;
+        toml::from_str(&content).ok()
+    }
+
+    /// Saves the session to the file, creating its directory if needed.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)
+                .with_context(|| format!("failed to create `{}`", dir.display()))?;
+        }
+
+        let content = toml::to_string(self)?;
+        fs::write(path, content).with_context(|| format!("failed to write `{}`", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn test_serialize_session() {
+        let session = Session {
+            letters: "radar*".to_string(),
+            regex: r#"^c.\"t$"#.to_string(),
+        };
+
+        let content = toml::to_string(&session).expect("session is serializable");
+
+        assert_eq!(toml::from_str::<Session>(&content).ok(), Some(session));
+    }
+
+    #[test]
+    fn test_ignore_corrupt_session() {
+        assert_eq!(toml::from_str::<Session>("letters = [").ok(), None);
+        assert_eq!(Session::load(Path::new("./does/not/exist.toml")), None);
+    }
+}
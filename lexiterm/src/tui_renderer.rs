@@ -3,18 +3,20 @@
 mod panels;
 
 use crate::app_manager::*;
+use crate::config::Theme;
 use anyhow::Result;
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
 use panels::*;
 use ratatui::layout::Layout as RatatuiLayout;
-use ratatui::layout::{Constraint::*, Rect};
+use ratatui::layout::{Constraint::*, Position, Rect};
 use ratatui::prelude::CrosstermBackend;
 use ratatui::style::{Style, Stylize};
-use ratatui::widgets::{Block, Padding, Paragraph, Wrap};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, BorderType, Padding, Paragraph, Wrap};
 use ratatui::Frame;
 use ratatui::Terminal;
 use std::collections::HashMap;
-use std::io::Stdout;
-use std::sync::LazyLock;
+use std::io::{self, Stdout};
 
 pub struct TuiRenderer {
     terminal: Terminal<CrosstermBackend<Stdout>>,
@@ -23,10 +25,18 @@ pub struct TuiRenderer {
 impl Default for TuiRenderer {
     fn default() -> Self {
         let terminal = ratatui::init();
+        // Clicking the panels is only a convenience so the app still works without it
+        let _ = crossterm::execute!(io::stdout(), EnableMouseCapture);
         Self { terminal }
     }
 }
 
+impl Drop for TuiRenderer {
+    fn drop(&mut self) {
+        let _ = crossterm::execute!(io::stdout(), DisableMouseCapture);
+    }
+}
+
 impl TuiRenderer {
     pub fn draw_frame(&mut self, state_mngr: &AppManager) -> Result<()> {
         let _result = self
@@ -40,48 +50,90 @@ impl TuiRenderer {
 struct Layout {
     letters: Rect,
     regex: Rect,
+    length: Rect,
     words: Rect,
+    status: Rect,
 }
 
 impl Layout {
-    pub fn build(frame: &mut Frame) -> Self {
+    pub fn build(area: Rect) -> Self {
         let padding = Block::default().padding(Padding::uniform(1));
-        let padded_area = padding.inner(frame.area());
+        let padded_area = padding.inner(area);
 
-        let [top, words] = RatatuiLayout::vertical([Length(3), Fill(1)]).areas(padded_area);
-        let [letters, regex] = RatatuiLayout::horizontal([Fill(1), Fill(1)]).areas(top);
+        let [top, words, status] =
+            RatatuiLayout::vertical([Length(3), Fill(1), Length(1)]).areas(padded_area);
+        let [letters, regex, length] =
+            RatatuiLayout::horizontal([Fill(1), Fill(1), Length(14)]).areas(top);
 
         Self {
             letters,
             regex,
+            length,
             words,
+            status,
         }
     }
 }
 
+/// Returns the [`PanelKind`] of the panel at the given position of a terminal with the
+/// given `area`.
+pub fn panel_at(area: Rect, column: u16, row: u16) -> Option<PanelKind> {
+    let layout = Layout::build(area);
+    let position = Position::new(column, row);
+
+    [
+        (layout.letters, PanelKind::Letters),
+        (layout.regex, PanelKind::Regex),
+        (layout.length, PanelKind::Length),
+        (layout.words, PanelKind::Words),
+    ]
+    .into_iter()
+    .find_map(|(rect, kind)| rect.contains(position).then_some(kind))
+}
+
 /// Handles the layout and rendering of UI components.
 fn render_callback(frame: &mut Frame, mngr: &AppManager) {
-    let layout = Layout::build(frame);
+    let layout = Layout::build(frame.area());
     let hints = generate_hints(mngr.get_linked_panels());
 
     LettersInputPanel::new(mngr, &hints).render(frame, layout.letters);
     RegexInputPanel::new(mngr, &hints).render(frame, layout.regex);
+    LengthInputPanel::new(mngr, &hints).render(frame, layout.length);
     WordsOutputPanel::new(mngr, &hints).render(frame, layout.words);
+    StatusLine::new(mngr).render(frame, layout.status);
+
+    if mngr.is_help_shown() {
+        HelpOverlay.render(frame, frame.area());
+    }
+    if mngr.is_confirming_quit() {
+        QuitOverlay.render(frame, frame.area());
+    }
 }
 
 trait Highlight {
-    fn highlight(self, state: PanelState) -> Self;
+    fn highlight(self, state: PanelState, theme: Theme, no_color: bool) -> Self;
 }
 
 impl Highlight for Block<'_> {
-    fn highlight(self, state: PanelState) -> Self {
-        static RED: LazyLock<Style> = LazyLock::new(|| Style::new().red());
-        static YELLOW: LazyLock<Style> = LazyLock::new(|| Style::new().yellow());
+    /// Conveys the [`PanelState`] through the border color of the [`Theme`] or, if
+    /// `no_color` is set, through the border characters and a text marker in the title.
+    fn highlight(self, state: PanelState, theme: Theme, no_color: bool) -> Self {
+        if no_color {
+            return match state {
+                PanelState::Default => self,
+                PanelState::Selected => self
+                    .border_type(BorderType::Double)
+                    .title(Line::from("[SELECTED]").right_aligned()),
+                PanelState::Error => self
+                    .border_type(BorderType::Thick)
+                    .title(Line::from("[ERROR]").right_aligned()),
+            };
+        }
 
         match state {
             PanelState::Default => self,
-            PanelState::Selected => self.border_style(*YELLOW),
-            PanelState::Error => self.border_style(*RED),
+            PanelState::Selected => self.border_style(Style::new().fg(theme.selected)),
+            PanelState::Error => self.border_style(Style::new().fg(theme.error)),
         }
     }
 }
@@ -103,3 +155,132 @@ fn generate_hints(linked: HashMap<Direction, PanelRef>) -> HashMap<PanelKind, ch
         .map(|(dir, panel)| (panel.kind(), dir.to_char()))
         .collect()
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::config::ResultFormat;
+    use pretty_assertions::assert_eq;
+    use ratatui::backend::TestBackend;
+    use std::time::Duration;
+
+    /// Renders the app into an in-memory buffer and returns its content as text.
+    fn render_to_string(mngr: &AppManager) -> String {
+        let mut terminal = Terminal::new(TestBackend::new(80, 12)).expect("a test terminal");
+        terminal
+            .draw(|frame| render_callback(frame, mngr))
+            .expect("a drawn frame");
+
+        let buffer = terminal.backend().buffer();
+        buffer
+            .content()
+            .chunks(buffer.area.width as usize)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_no_color_selected_marker() {
+        let mut mngr = AppManager::default();
+
+        assert!(!render_to_string(&mngr).contains("[SELECTED]"));
+
+        mngr.set_no_color(true);
+        let rendered = render_to_string(&mngr);
+
+        assert!(rendered.contains("[SELECTED]"));
+        assert!(rendered.contains('╔'), "selected border should be doubled");
+    }
+
+    #[test]
+    fn test_no_color_error_marker() {
+        let mut mngr = AppManager::default();
+        mngr.select_panel(Direction::Right);
+        "[a-".chars().for_each(|ch| {
+            mngr.push_ch(ch);
+        });
+
+        assert!(!render_to_string(&mngr).contains("[ERROR]"));
+
+        mngr.set_no_color(true);
+        let rendered = render_to_string(&mngr);
+
+        assert!(rendered.contains("[ERROR]"));
+        assert!(rendered.contains('┏'), "error border should be thick");
+    }
+
+    #[test]
+    fn test_theme_colors_the_selected_block() {
+        let theme = Theme::HIGH_CONTRAST;
+
+        assert_eq!(
+            Block::bordered().highlight(PanelState::Selected, theme, false),
+            Block::bordered().border_style(Style::new().fg(theme.selected))
+        );
+        assert_eq!(
+            Block::bordered().highlight(PanelState::Default, theme, false),
+            Block::bordered()
+        );
+    }
+
+    #[test]
+    fn test_no_color_error_is_marked_with_text() {
+        let block = Block::bordered().highlight(PanelState::Error, Theme::DEFAULT, true);
+
+        assert_eq!(
+            block,
+            Block::bordered()
+                .border_type(BorderType::Thick)
+                .title(Line::from("[ERROR]").right_aligned())
+        );
+    }
+
+    #[test]
+    fn test_panel_at() {
+        let area = Rect::new(0, 0, 80, 12);
+
+        assert_eq!(panel_at(area, 5, 2), Some(PanelKind::Letters));
+        assert_eq!(panel_at(area, 40, 2), Some(PanelKind::Regex));
+        assert_eq!(panel_at(area, 75, 2), Some(PanelKind::Length));
+        assert_eq!(panel_at(area, 40, 6), Some(PanelKind::Words));
+        // the padding around the panels
+        assert_eq!(panel_at(area, 0, 0), None);
+    }
+
+    #[test]
+    fn test_help_overlay() {
+        let mut mngr = AppManager::default();
+
+        assert!(!render_to_string(&mngr).contains("Keybindings"));
+
+        mngr.toggle_help();
+
+        assert!(render_to_string(&mngr).contains("Keybindings"));
+    }
+
+    #[test]
+    fn test_status_line_shows_query_latency() {
+        let mut mngr = AppManager::default();
+
+        assert!(!render_to_string(&mngr).contains("results in"));
+
+        mngr.set_output_words(vec![("cat".to_string(), 5), ("act".to_string(), 5)]);
+        mngr.set_query_elapsed(Duration::from_millis(8));
+
+        assert!(render_to_string(&mngr).contains("2 results in 8ms"));
+    }
+
+    #[test]
+    fn test_words_use_result_format() {
+        let mut mngr = AppManager::default();
+        mngr.set_output_words(vec![("cat".to_string(), 5), ("act".to_string(), 5)]);
+
+        assert!(render_to_string(&mngr).contains("cat:5, act:5"));
+
+        let result_format = ResultFormat::try_from("{word} ({score})".to_string()).unwrap();
+        mngr.set_result_format(result_format);
+
+        assert!(render_to_string(&mngr).contains("cat (5), act (5)"));
+    }
+}
@@ -1,5 +1,8 @@
 use super::*;
-use ratatui::layout::Rect;
+use crate::config::{ResultFormat, Segment, Theme};
+use ratatui::layout::{Flex, Rect};
+use ratatui::text::Span;
+use ratatui::widgets::Clear;
 
 #[derive(Clone, Copy, Default)]
 pub enum PanelState {
@@ -9,9 +12,23 @@ pub enum PanelState {
     Error,
 }
 
+impl PanelState {
+    /// Returns [`PanelState::Selected`] if the panel of the given [`PanelKind`] is
+    /// selected.
+    fn of(mngr: &AppManager, kind: PanelKind) -> Self {
+        if mngr.selected_panel().kind() == kind {
+            PanelState::Selected
+        } else {
+            PanelState::Default
+        }
+    }
+}
+
 pub struct LettersInputPanel<'a> {
     title: String,
     state: PanelState,
+    no_color: bool,
+    theme: Theme,
     letters: &'a str,
 }
 
@@ -21,22 +38,24 @@ impl<'a> LettersInputPanel<'a> {
         let title = hint
             .map(|hint| format!("Letters ({hint})"))
             .unwrap_or_else(|| "Letters".to_string());
-        let state = (mngr.selected_panel().kind() == PanelKind::Letters)
-            .then_some(PanelState::Selected)
-            .unwrap_or_default();
+        let state = PanelState::of(mngr, PanelKind::Letters);
         let letters = mngr.get_input_letters();
 
         Self {
             title,
             state,
+            no_color: mngr.is_no_color(),
+            theme: mngr.get_theme(),
             letters,
         }
     }
 
     pub fn render(self, frame: &mut Frame, rect: Rect) {
-        let block = Block::bordered()
-            .title(self.title.as_ref())
-            .highlight(self.state);
+        let block = Block::bordered().title(self.title.as_ref()).highlight(
+            self.state,
+            self.theme,
+            self.no_color,
+        );
         frame.render_widget(Paragraph::new(self.letters).block(block), rect);
     }
 }
@@ -44,19 +63,25 @@ impl<'a> LettersInputPanel<'a> {
 pub struct RegexInputPanel<'a> {
     title: String,
     state: PanelState,
+    no_color: bool,
+    theme: Theme,
     regex: &'a str,
+    error: Option<&'a str>,
 }
 
 impl<'a> RegexInputPanel<'a> {
     pub fn new(mngr: &'a AppManager, hints: &'_ HashMap<PanelKind, char>) -> RegexInputPanel<'a> {
         let hint = hints.get(&PanelKind::Regex).copied();
-        let title = hint
-            .map(|hint| format!("Regex ({hint})"))
-            .unwrap_or_else(|| "Regex".to_string());
+        let mut title = match mngr.get_regex_match_count() {
+            Some(1) => "Regex (1 match)".to_string(),
+            Some(count) => format!("Regex ({count} matches)"),
+            None => "Regex".to_string(),
+        };
+        if let Some(hint) = hint {
+            title.push_str(&format!(" ({hint})"));
+        }
         let state = if mngr.is_regex_valid() {
-            (mngr.selected_panel().kind() == PanelKind::Regex)
-                .then_some(PanelState::Selected)
-                .unwrap_or_default()
+            PanelState::of(mngr, PanelKind::Regex)
         } else {
             PanelState::Error
         };
@@ -65,51 +90,468 @@ impl<'a> RegexInputPanel<'a> {
         Self {
             title,
             state,
+            no_color: mngr.is_no_color(),
+            theme: mngr.get_theme(),
             regex,
+            error: mngr.get_regex_error().map(summarize_regex_error),
         }
     }
 
     pub fn render(self, frame: &mut Frame, rect: Rect) {
-        let block = Block::bordered()
-            .title(self.title.as_ref())
-            .highlight(self.state);
+        let mut block = Block::bordered().title(self.title.as_ref()).highlight(
+            self.state,
+            self.theme,
+            self.no_color,
+        );
+        if let Some(error) = self.error {
+            block = block.title_bottom(error);
+        }
         frame.render_widget(Paragraph::new(self.regex).block(block), rect);
     }
 }
 
+/// Returns the last line of a [`regex::Error`] message, which says what is wrong without
+/// pointing at where, so it fits on a single line.
+fn summarize_regex_error(err: &str) -> &str {
+    let line = err.lines().last().unwrap_or_default().trim();
+    line.strip_prefix("error: ").unwrap_or(line)
+}
+
+pub struct LengthInputPanel<'a> {
+    title: String,
+    state: PanelState,
+    no_color: bool,
+    theme: Theme,
+    length: &'a str,
+}
+
+impl<'a> LengthInputPanel<'a> {
+    pub fn new(mngr: &'a AppManager, hints: &'_ HashMap<PanelKind, char>) -> LengthInputPanel<'a> {
+        let hint = hints.get(&PanelKind::Length).copied();
+        let title = hint
+            .map(|hint| format!("Length ({hint})"))
+            .unwrap_or_else(|| "Length".to_string());
+        let state = if mngr.is_length_valid() {
+            PanelState::of(mngr, PanelKind::Length)
+        } else {
+            PanelState::Error
+        };
+        let length = mngr.get_input_length();
+
+        Self {
+            title,
+            state,
+            no_color: mngr.is_no_color(),
+            theme: mngr.get_theme(),
+            length,
+        }
+    }
+
+    pub fn render(self, frame: &mut Frame, rect: Rect) {
+        let block = Block::bordered().title(self.title.as_ref()).highlight(
+            self.state,
+            self.theme,
+            self.no_color,
+        );
+        frame.render_widget(Paragraph::new(self.length).block(block), rect);
+    }
+}
+
 pub struct WordsOutputPanel<'a> {
     title: String,
     state: PanelState,
-    words: &'a Vec<String>,
+    no_color: bool,
+    theme: Theme,
+    words: &'a [(String, u32)],
+    result_format: &'a ResultFormat,
+    layout: WordsLayout,
+    selected_word: usize,
+    placeholder: Option<&'static str>,
 }
 
 impl<'a> WordsOutputPanel<'a> {
     pub fn new(mngr: &'a AppManager, hints: &'_ HashMap<PanelKind, char>) -> WordsOutputPanel<'a> {
         let hint = hints.get(&PanelKind::Words).copied();
-        let title = hint
-            .map(|hint| format!("Words ({hint})"))
-            .unwrap_or_else(|| "Words".to_string());
-        let state = (mngr.selected_panel().kind() == PanelKind::Words)
-            .then_some(PanelState::Selected)
-            .unwrap_or_default();
         let words = mngr.get_ouput_words();
+        let title = words_title(words.len(), mngr.get_sort_mode(), hint);
+        let state = PanelState::of(mngr, PanelKind::Words);
 
         Self {
             title,
             state,
+            no_color: mngr.is_no_color(),
+            theme: mngr.get_theme(),
             words,
+            result_format: mngr.result_format(),
+            layout: mngr.get_words_layout(),
+            selected_word: mngr.get_selected_word_idx(),
+            placeholder: words_placeholder(words.is_empty(), mngr.has_query(), mngr.is_searching()),
         }
     }
 
     pub fn render(self, frame: &mut Frame, rect: Rect) {
+        let block = Block::bordered().title(self.title.as_ref()).highlight(
+            self.state,
+            self.theme,
+            self.no_color,
+        );
+        if let Some(placeholder) = self.placeholder {
+            let placeholder = Paragraph::new(placeholder.dim()).block(block);
+            frame.render_widget(placeholder, rect);
+            return;
+        }
+
+        let is_selected = matches!(self.state, PanelState::Selected);
+        let selected_word = is_selected.then_some(self.selected_word);
+        let lines = words_lines(self.words, self.result_format, self.layout, selected_word);
+
+        let paragraph = match self.layout {
+            WordsLayout::Joined => Paragraph::new(lines).wrap(Wrap { trim: false }),
+            WordsLayout::OnePerLine => {
+                // Scroll just enough to keep the selected word in view
+                let height = block.inner(rect).height as usize;
+                let scroll = (self.selected_word + 1).saturating_sub(height);
+                Paragraph::new(lines).scroll((scroll as u16, 0))
+            }
+        };
+        frame.render_widget(paragraph.block(block), rect);
+    }
+}
+
+/// Returns the text shown instead of the words, telling apart why there are none.
+fn words_placeholder(is_empty: bool, has_query: bool, searching: bool) -> Option<&'static str> {
+    match (is_empty, has_query, searching) {
+        (false, _, _) => None,
+        (true, false, _) => Some("Type some letters or a regex to search"),
+        (true, true, true) => Some("Searching..."),
+        (true, true, false) => Some("No matches"),
+    }
+}
+
+/// Builds the lines of the Words panel, reversing the colors of the `selected_word`.
+fn words_lines<'a>(
+    words: &'a [(String, u32)],
+    result_format: &'a ResultFormat,
+    layout: WordsLayout,
+    selected_word: Option<usize>,
+) -> Vec<Line<'a>> {
+    let results = words.iter().enumerate().map(|(i, (word, score))| {
+        let result = result_spans(result_format, word, *score);
+        if selected_word == Some(i) {
+            result.into_iter().map(Stylize::reversed).collect()
+        } else {
+            result
+        }
+    });
+
+    match layout {
+        WordsLayout::Joined => {
+            let mut spans = Vec::with_capacity(words.len() * 4);
+            for (i, result) in results.enumerate() {
+                if i > 0 {
+                    spans.push(Span::raw(", "));
+                }
+                spans.extend(result);
+            }
+            vec![Line::from(spans)]
+        }
+        WordsLayout::OnePerLine => results.map(Line::from).collect(),
+    }
+}
+
+/// A centered popup listing the keybindings.
+pub struct HelpOverlay;
+
+impl HelpOverlay {
+    const KEYBINDINGS: [(&'static str, &'static str); 15] = [
+        ("← ↑ → ↓", "select a panel or a word"),
+        ("Backspace", "delete the last character"),
+        ("Ctrl-W", "delete the last word"),
+        ("Ctrl-U", "clear the panel"),
+        ("Ctrl-L", "clear every panel"),
+        ("Enter", "remember the rack (Letters)"),
+        ("Ctrl-P / N", "recall an older or newer rack (Letters)"),
+        ("s", "sort by score or a-z (Words)"),
+        ("w", "one word per line (Words)"),
+        ("y / Enter", "copy the selected word (Words)"),
+        ("? / F1", "show or hide this help"),
+        ("i / Esc", "type into the panels or stop typing"),
+        ("h j k l", "select a panel or a word (normal mode)"),
+        ("Esc", "close this help, clear the panel, or quit"),
+        ("q / Ctrl-C", "quit (normal mode) or quit anytime"),
+    ];
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let lines = Self::KEYBINDINGS
+            .iter()
+            .map(|(keys, action)| Line::from(format!("{keys:>10}  {action}")))
+            .collect::<Vec<_>>();
+
+        let height = lines.len() as u16 + 2;
+        let width = lines.iter().map(Line::width).max().unwrap_or_default() as u16 + 4;
+        let area = centered(area, width, height);
+
         let block = Block::bordered()
-            .title(self.title.as_ref())
-            .highlight(self.state);
-        frame.render_widget(
-            Paragraph::new(self.words.join(", "))
-                .wrap(Wrap { trim: false })
-                .block(block),
-            rect,
+            .title("Keybindings")
+            .padding(Padding::horizontal(1));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+    }
+}
+
+/// A centered popup asking to confirm quitting.
+pub struct QuitOverlay;
+
+impl QuitOverlay {
+    const QUESTION: &'static str = "Quit lexiterm? (y/n)";
+
+    pub fn render(self, frame: &mut Frame, area: Rect) {
+        let width = Self::QUESTION.chars().count() as u16 + 4;
+        let area = centered(area, width, 3);
+
+        let block = Block::bordered().padding(Padding::horizontal(1));
+        frame.render_widget(Clear, area);
+        frame.render_widget(Paragraph::new(Self::QUESTION).block(block), area);
+    }
+}
+
+/// Returns a `width` by `height` area in the middle of the `area`.
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let [area] = RatatuiLayout::vertical([Length(height)])
+        .flex(Flex::Center)
+        .areas(area);
+    let [area] = RatatuiLayout::horizontal([Length(width)])
+        .flex(Flex::Center)
+        .areas(area);
+    area
+}
+
+/// Writes a result using the [`ResultFormat`], with the score dimmed so the words stand
+/// out.
+fn result_spans<'a>(result_format: &'a ResultFormat, word: &'a str, score: u32) -> Vec<Span<'a>> {
+    result_format
+        .segments()
+        .map(|segment| match segment {
+            Segment::Text(text) => Span::raw(text),
+            Segment::Word => Span::raw(word),
+            Segment::Score => Span::raw(score.to_string()).dim(),
+        })
+        .collect()
+}
+
+/// Builds the title of the Words panel, e.g. `Words (42, by score)` or
+/// `Words (42, by score) (↓)` if the panel can be reached with an arrow key.
+fn words_title(count: usize, sort_mode: SortMode, hint: Option<char>) -> String {
+    let sort_mode = match sort_mode {
+        SortMode::Score => "by score",
+        SortMode::Alpha => "a-z",
+    };
+
+    match hint {
+        Some(hint) => format!("Words ({count}, {sort_mode}) ({hint})"),
+        None => format!("Words ({count}, {sort_mode})"),
+    }
+}
+
+/// The line at the bottom showing the rack size, the number of results, and why the
+/// input can't be searched, if it can't.
+pub struct StatusLine {
+    info: String,
+    error: Option<String>,
+    no_color: bool,
+    theme: Theme,
+}
+
+impl StatusLine {
+    pub fn new(mngr: &AppManager) -> Self {
+        let mode = match mngr.get_input_mode() {
+            InputMode::Normal => "NORMAL",
+            InputMode::Insert => "INSERT",
+        };
+        let tiles = mngr.get_input_letters().chars().count();
+        let mut info = match tiles {
+            1 => format!("{mode} | 1 tile"),
+            tiles => format!("{mode} | {tiles} tiles"),
+        };
+
+        if let Some(elapsed) = mngr.get_query_elapsed() {
+            let results = match mngr.get_ouput_words().len() {
+                1 => "1 result".to_string(),
+                results => format!("{results} results"),
+            };
+            if mngr.is_search_partial() {
+                info.push_str(&format!(" | {results} so far, searching..."));
+            } else {
+                info.push_str(&format!(" | {results} in {}ms", elapsed.as_millis()));
+            }
+        }
+        if let Some(frame) = mngr.get_spinner_frame() {
+            info.push_str(&format!(" {frame}"));
+        }
+
+        let error = if let Some(err) = mngr.get_regex_error() {
+            Some(format!("invalid regex: {}", summarize_regex_error(err)))
+        } else if !mngr.is_length_valid() {
+            Some("invalid length, use a length like 5 or a range like 3-7".to_string())
+        } else {
+            None
+        };
+
+        Self {
+            info,
+            error,
+            no_color: mngr.is_no_color(),
+            theme: mngr.get_theme(),
+        }
+    }
+
+    pub fn render(self, frame: &mut Frame, rect: Rect) {
+        let mut spans = vec![Span::raw(self.info)];
+        if let Some(error) = self.error {
+            let style = if self.no_color {
+                Style::new()
+            } else {
+                Style::new().fg(self.theme.error)
+            };
+            spans.push(Span::raw(" | "));
+            spans.push(Span::styled(format!("error: {error}"), style));
+        }
+
+        frame.render_widget(Paragraph::new(Line::from(spans)), rect);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::time::Duration;
+
+    #[test]
+    fn test_status_line() {
+        let mut mngr = AppManager::default();
+
+        let status = StatusLine::new(&mngr);
+        assert_eq!(status.info, "INSERT | 0 tiles");
+        assert_eq!(status.error, None);
+
+        mngr.push_ch('a');
+        assert_eq!(StatusLine::new(&mngr).info, "INSERT | 1 tile");
+
+        "ñ*".chars().for_each(|ch| {
+            mngr.push_ch(ch);
+        });
+        mngr.set_output_words(vec![("año".to_string(), 3)]);
+        mngr.set_query_elapsed(Duration::from_millis(12));
+        assert_eq!(
+            StatusLine::new(&mngr).info,
+            "INSERT | 3 tiles | 1 result in 12ms"
+        );
+
+        mngr.set_search_partial(true);
+        assert_eq!(
+            StatusLine::new(&mngr).info,
+            "INSERT | 3 tiles | 1 result so far, searching..."
+        );
+        mngr.set_search_partial(false);
+
+        mngr.set_searching(true);
+        assert_eq!(
+            StatusLine::new(&mngr).info,
+            "INSERT | 3 tiles | 1 result in 12ms ⠋"
+        );
+        mngr.set_searching(false);
+
+        mngr.set_output_words(vec![("año".to_string(), 3), ("ano".to_string(), 3)]);
+        assert_eq!(
+            StatusLine::new(&mngr).info,
+            "INSERT | 3 tiles | 2 results in 12ms"
+        );
+
+        mngr.select_panel(Direction::Right);
+        mngr.push_ch('(');
+        assert_eq!(
+            StatusLine::new(&mngr).error.as_deref(),
+            Some("invalid regex: unclosed group")
+        );
+    }
+
+    #[test]
+    fn test_summarize_regex_error() {
+        let err = "regex parse error:\n    [a-\n    ^^\nerror: unclosed character class";
+
+        assert_eq!(summarize_regex_error(err), "unclosed character class");
+    }
+
+    #[test]
+    fn test_result_spans() {
+        let result_format = ResultFormat::default();
+        assert_eq!(
+            result_spans(&result_format, "radar", 6),
+            [Span::raw("radar"), Span::raw(":"), Span::raw("6").dim()]
+        );
+
+        let result_format = ResultFormat::try_from("{word} ({score})".to_string()).unwrap();
+        assert_eq!(
+            result_spans(&result_format, "año", 3),
+            [
+                Span::raw("año"),
+                Span::raw(" ("),
+                Span::raw("3").dim(),
+                Span::raw(")")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_words_lines() {
+        let words = [("radar".to_string(), 6), ("cart".to_string(), 6)];
+        let result_format = ResultFormat::default();
+        let text = |lines: Vec<Line>| lines.iter().map(Line::to_string).collect::<Vec<_>>();
+
+        assert_eq!(
+            text(words_lines(
+                &words,
+                &result_format,
+                WordsLayout::Joined,
+                None
+            )),
+            ["radar:6, cart:6"]
+        );
+        assert_eq!(
+            text(words_lines(
+                &words,
+                &result_format,
+                WordsLayout::OnePerLine,
+                None
+            )),
+            ["radar:6", "cart:6"]
+        );
+
+        let lines = words_lines(&words, &result_format, WordsLayout::OnePerLine, Some(1));
+        assert_eq!(lines[1].spans[0], Span::raw("cart").reversed());
+    }
+
+    #[test]
+    fn test_words_placeholder() {
+        assert_eq!(
+            words_placeholder(true, false, false),
+            Some("Type some letters or a regex to search")
+        );
+        assert_eq!(words_placeholder(true, true, true), Some("Searching..."));
+        assert_eq!(words_placeholder(true, true, false), Some("No matches"));
+        assert_eq!(words_placeholder(false, true, true), None);
+    }
+
+    #[test]
+    fn test_words_title() {
+        assert_eq!(
+            words_title(42, SortMode::Score, None),
+            "Words (42, by score)"
+        );
+        assert_eq!(
+            words_title(0, SortMode::Alpha, Some('↓')),
+            "Words (0, a-z) (↓)"
         );
     }
 }
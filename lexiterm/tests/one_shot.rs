@@ -0,0 +1,104 @@
+use pretty_assertions::assert_eq;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Builds a lexiterm command that loads the test word list, and the config file at the
+/// path instead of the one of the user running the tests.
+fn lexiterm_command(config: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_lexiterm"));
+    command
+        .args(["--words", "./test_data/words.txt"])
+        .args(["--scores", "./test_data/scores.txt"])
+        .arg("--config")
+        .arg(config);
+    command
+}
+
+/// Returns a path to a config file that doesn't exist so the defaults are used.
+fn missing_config() -> PathBuf {
+    std::env::temp_dir().join(format!(
+        "lexiterm_test_missing_config_{}.toml",
+        std::process::id()
+    ))
+}
+
+fn run_lexiterm(args: &[&str]) -> String {
+    let output = lexiterm_command(&missing_config())
+        .args(args)
+        .output()
+        .expect("run lexiterm");
+
+    assert!(output.status.success(), "{output:?}");
+    String::from_utf8(output.stdout).expect("stdout is valid utf-8")
+}
+
+#[test]
+fn test_query_prints_scored_words() {
+    let stdout = run_lexiterm(&["--query", "radartc"]);
+
+    assert_eq!(stdout, "radar:6\ncart:6\ndart:5\ncar:5\nrad:4\n");
+}
+
+#[test]
+fn test_query_with_regex() {
+    let stdout = run_lexiterm(&["--query", "radartc", "--regex", "^ca"]);
+
+    assert_eq!(stdout, "cart:6\ncar:5\n");
+}
+
+#[test]
+fn test_query_sorted_alphabetically_with_limit() {
+    let stdout = run_lexiterm(&["--query", "radartc", "--sort", "alpha", "--limit", "3"]);
+
+    assert_eq!(stdout, "car:5\ncart:6\ndart:5\n");
+}
+
+#[test]
+fn test_query_as_json() {
+    let stdout = run_lexiterm(&["--query", "radartc", "--format", "json", "--limit", "3"]);
+
+    let results: serde_json::Value = serde_json::from_str(&stdout).expect("valid json");
+    let results = results.as_array().expect("json array");
+    let results = results
+        .iter()
+        .map(|result| {
+            (
+                result["word"].as_str().expect("word is a string"),
+                result["score"].as_u64().expect("score is a number"),
+            )
+        })
+        .collect::<Vec<_>>();
+
+    assert_eq!(results, vec![("radar", 6), ("cart", 6), ("dart", 5)]);
+}
+
+#[test]
+fn test_query_with_invalid_regex_fails() {
+    let output = lexiterm_command(&missing_config())
+        .args(["--query", "radartc", "--regex", "("])
+        .output()
+        .expect("run lexiterm");
+
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_query_uses_the_result_format_of_the_config() {
+    let config = std::env::temp_dir().join(format!(
+        "lexiterm_test_result_format_config_{}.toml",
+        std::process::id()
+    ));
+    std::fs::write(&config, r#"result_format = "{score} - {word}""#).expect("write the config");
+
+    let output = lexiterm_command(&config)
+        .args(["--query", "radartc", "--limit", "2"])
+        .output()
+        .expect("run lexiterm");
+    std::fs::remove_file(&config).expect("remove the config");
+
+    assert!(output.status.success(), "{output:?}");
+    assert_eq!(
+        String::from_utf8(output.stdout).expect("stdout is valid utf-8"),
+        "6 - radar\n6 - cart\n"
+    );
+}